@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use k8s::openapi::jiff::Timestamp;
+
+use super::*;
+
+/// Age of `obj`, i.e. time elapsed since its `metadata.creationTimestamp`
+/// Return `None` when the creation timestamp is not set (e.g. object not created yet),
+/// objects created "in the future" because of clock skew are zero seconds old
+///
+/// ```
+/// # use k8s_openapi::api::core::v1::Pod;
+/// # use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+/// use kube_client_ext::resource_age;
+///
+/// let mut pod = Pod::default();
+/// assert_eq!(resource_age(&pod), None);
+///
+/// let created = "2020-01-01T00:00:00Z".parse().unwrap();
+/// pod.metadata.creation_timestamp = Some(Time(created));
+/// assert!(resource_age(&pod).is_some_and(|age| age.as_secs() > 365 * 24 * 3600));
+/// ```
+///
+pub fn resource_age(obj: &impl client::ResourceExt) -> Option<Duration> {
+    let created = obj.creation_timestamp()?.0;
+    let age = Timestamp::now().duration_since(created);
+    Some(Duration::try_from(age).unwrap_or_default())
+}
+
+/// Check whether `obj` has been created more than `age` ago
+/// Objects without creation timestamp are never older than anything
+///
+/// ```
+/// # use std::time::Duration;
+/// # use k8s_openapi::api::batch::v1::Job;
+/// # use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+/// use kube_client_ext::is_older_than;
+///
+/// const WEEK: Duration = Duration::from_secs(7 * 24 * 3600);
+///
+/// let mut job = Job::default();
+/// assert!(!is_older_than(&job, WEEK));
+///
+/// job.metadata.creation_timestamp = Some(Time("2020-01-01T00:00:00Z".parse().unwrap()));
+/// assert!(is_older_than(&job, WEEK));
+///
+/// job.metadata.creation_timestamp = Some(Time(k8s_openapi::jiff::Timestamp::now()));
+/// assert!(!is_older_than(&job, WEEK));
+/// ```
+///
+pub fn is_older_than(obj: &impl client::ResourceExt, age: Duration) -> bool {
+    resource_age(obj).is_some_and(|actual| actual > age)
+}
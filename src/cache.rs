@@ -0,0 +1,429 @@
+use std::fmt;
+use std::future;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use futures::FutureExt as _;
+use futures::StreamExt as _;
+use futures::future::BoxFuture;
+use kube_runtime::WatchStreamExt as _;
+use kube_runtime::reflector;
+use kube_runtime::watcher;
+
+use super::*;
+
+/// Cache backed `get`/`list` for read-heavy tools
+///
+/// Kinds enabled with `with_*()` are mirrored by reflectors running in the background
+/// and served from memory, either cluster-wide ([`Cached::new`]) or in a single namespace
+/// ([`Cached::namespaced`]). Kinds that are not cached or not synced yet, objects missing
+/// from the cache and namespaces outside of a namespaced cache are fetched from the live
+/// API instead. Use [`Cached::wait_until_ready`] to wait for the initial sync.
+///
+/// Background watchers are spawned on the current Tokio runtime on the first read
+/// (or [`Cached::wait_until_ready`]), and are stopped once the last clone of `Cached`
+/// is dropped.
+///
+/// Cluster-wide watches require permission to list and watch the kind in all namespaces.
+/// Without it the watchers keep failing and retrying, and the cache never becomes ready,
+/// so callers with namespace-scoped RBAC should use [`Cached::namespaced`].
+///
+#[derive(Clone)]
+pub struct Cached {
+    client: client::Client,
+    namespace: Option<String>,
+    configmaps: Option<reflector::Store<corev1::ConfigMap>>,
+    deployments: Option<reflector::Store<appsv1::Deployment>>,
+    pods: Option<reflector::Store<corev1::Pod>>,
+    services: Option<reflector::Store<corev1::Service>>,
+    tasks: Vec<Arc<Task>>,
+}
+
+impl fmt::Debug for Cached {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cached")
+            .field("namespace", &self.namespace)
+            .field("configmaps", &self.configmaps)
+            .field("deployments", &self.deployments)
+            .field("pods", &self.pods)
+            .field("services", &self.services)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Cached {
+    /// Cache mirroring enabled kinds across all namespaces
+    pub fn new(client: client::Client) -> Self {
+        Self {
+            client,
+            namespace: None,
+            configmaps: None,
+            deployments: None,
+            pods: None,
+            services: None,
+            tasks: Vec::new(),
+        }
+    }
+
+    /// Cache mirroring enabled kinds in `namespace` only, other namespaces
+    /// are always fetched from the live API
+    pub fn namespaced(client: client::Client, namespace: &str) -> Self {
+        Self {
+            namespace: Some(namespace.to_string()),
+            ..Self::new(client)
+        }
+    }
+
+    /// Underlying client used for cache misses
+    pub fn client(&self) -> &client::Client {
+        &self.client
+    }
+
+    pub fn with_configmaps(mut self) -> Self {
+        self.configmaps = Some(self.reflect());
+        self
+    }
+
+    pub fn with_deployments(mut self) -> Self {
+        self.deployments = Some(self.reflect());
+        self
+    }
+
+    pub fn with_pods(mut self) -> Self {
+        self.pods = Some(self.reflect());
+        self
+    }
+
+    pub fn with_services(mut self) -> Self {
+        self.services = Some(self.reflect());
+        self
+    }
+
+    /// Wait up to `timeout` until every enabled cache has completed its initial sync
+    /// Return `Timeout` error if it did not (e.g. the watches are not permitted)
+    pub async fn wait_until_ready(&self, timeout: Duration) -> client::Result<()> {
+        self.start();
+        let ready = async {
+            if let Some(store) = &self.configmaps {
+                store.wait_until_ready().await?;
+            }
+            if let Some(store) = &self.deployments {
+                store.wait_until_ready().await?;
+            }
+            if let Some(store) = &self.pods {
+                store.wait_until_ready().await?;
+            }
+            if let Some(store) = &self.services {
+                store.wait_until_ready().await?;
+            }
+            Ok::<_, reflector::store::WriterDropped>(())
+        };
+        match tokio::time::timeout(timeout, ready).await {
+            Ok(ready) => ready.map_err(|err| client::Error::Service(Box::new(err))),
+            Err(_) => {
+                let message = format!("cache did not sync within {timeout:?}");
+                Err(helper::timeout(&message))
+            }
+        }
+    }
+
+    pub async fn cached_get_configmap(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>>,
+    ) -> client::Result<Option<corev1::ConfigMap>> {
+        self.get_k(self.configmaps.as_ref(), name, namespace.into())
+            .await
+    }
+
+    pub async fn cached_list_configmaps(
+        &self,
+        namespace: impl Into<Option<&str>>,
+    ) -> client::Result<Vec<corev1::ConfigMap>> {
+        self.list_k(self.configmaps.as_ref(), namespace.into())
+            .await
+    }
+
+    pub async fn cached_get_deployment(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>>,
+    ) -> client::Result<Option<appsv1::Deployment>> {
+        self.get_k(self.deployments.as_ref(), name, namespace.into())
+            .await
+    }
+
+    pub async fn cached_list_deployments(
+        &self,
+        namespace: impl Into<Option<&str>>,
+    ) -> client::Result<Vec<appsv1::Deployment>> {
+        self.list_k(self.deployments.as_ref(), namespace.into())
+            .await
+    }
+
+    pub async fn cached_get_pod(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>>,
+    ) -> client::Result<Option<corev1::Pod>> {
+        self.get_k(self.pods.as_ref(), name, namespace.into()).await
+    }
+
+    pub async fn cached_list_pods(
+        &self,
+        namespace: impl Into<Option<&str>>,
+    ) -> client::Result<Vec<corev1::Pod>> {
+        self.list_k(self.pods.as_ref(), namespace.into()).await
+    }
+
+    pub async fn cached_get_service(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>>,
+    ) -> client::Result<Option<corev1::Service>> {
+        self.get_k(self.services.as_ref(), name, namespace.into())
+            .await
+    }
+
+    pub async fn cached_list_services(
+        &self,
+        namespace: impl Into<Option<&str>>,
+    ) -> client::Result<Vec<corev1::Service>> {
+        self.list_k(self.services.as_ref(), namespace.into()).await
+    }
+
+    fn reflect<K>(&mut self) -> reflector::Store<K>
+    where
+        K: Clone
+            + fmt::Debug
+            + Send
+            + Sync
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>
+            + 'static,
+        <K as client::Resource>::DynamicType: Clone + Default + Eq + Hash + Send + Sync,
+    {
+        let (reader, writer) = reflector::store();
+        let api = match &self.namespace {
+            Some(namespace) => self.client.namespaced_api::<K>(namespace),
+            None => self.client.api::<K>(),
+        };
+        let stream = watcher(api, watcher::Config::default())
+            .default_backoff()
+            .reflect(writer)
+            .for_each(|_| future::ready(()));
+        self.tasks.push(Arc::new(Task::new(stream.boxed())));
+        reader
+    }
+
+    /// Spawn the reflectors that are not running yet
+    fn start(&self) {
+        self.tasks.iter().for_each(|task| task.start());
+    }
+
+    async fn get_k<K>(
+        &self,
+        store: Option<&reflector::Store<K>>,
+        name: &str,
+        namespace: Option<&str>,
+    ) -> client::Result<Option<K>>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>
+            + 'static,
+        <K as client::Resource>::DynamicType: Clone + Default + Eq + Hash,
+    {
+        self.start();
+        let namespace = namespace.unwrap_or_else(|| self.client.default_namespace());
+        let key = reflector::ObjectRef::new(name).within(namespace);
+        if let Some(object) = self
+            .store(store, namespace)
+            .and_then(|store| store.get(&key))
+        {
+            Ok(Some(Arc::unwrap_or_clone(object)))
+        } else {
+            self.client.namespaced_api(namespace).get_opt(name).await
+        }
+    }
+
+    async fn list_k<K>(
+        &self,
+        store: Option<&reflector::Store<K>>,
+        namespace: Option<&str>,
+    ) -> client::Result<Vec<K>>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>
+            + 'static,
+        <K as client::Resource>::DynamicType: Clone + Default + Eq + Hash,
+    {
+        self.start();
+        let namespace = namespace.unwrap_or_else(|| self.client.default_namespace());
+        if let Some(store) = self.store(store, namespace) {
+            let items = store
+                .state()
+                .into_iter()
+                .filter(|object| object.meta().namespace.as_deref() == Some(namespace))
+                .map(Arc::unwrap_or_clone)
+                .collect();
+            Ok(items)
+        } else {
+            let lp = self.client.list_params();
+            self.client
+                .namespaced_api(namespace)
+                .list(&lp)
+                .await
+                .map(|list| list.items)
+        }
+    }
+
+    /// `store` if it has synced and mirrors `namespace`
+    fn store<'a, K>(
+        &self,
+        store: Option<&'a reflector::Store<K>>,
+        namespace: &str,
+    ) -> Option<&'a reflector::Store<K>>
+    where
+        K: Clone + client::Resource + 'static,
+        <K as client::Resource>::DynamicType: Clone + Eq + Hash,
+    {
+        store.filter(|store| {
+            let ready = store
+                .wait_until_ready()
+                .now_or_never()
+                .is_some_and(|ready| ready.is_ok());
+            ready
+                && self
+                    .namespace
+                    .as_deref()
+                    .is_none_or(|cached| cached == namespace)
+        })
+    }
+}
+
+/// Background reflector task, spawned on first use and aborted on drop
+struct Task {
+    pending: Mutex<Option<BoxFuture<'static, ()>>>,
+    running: OnceLock<tokio::task::AbortHandle>,
+}
+
+impl Task {
+    fn new(stream: BoxFuture<'static, ()>) -> Self {
+        Self {
+            pending: Mutex::new(Some(stream)),
+            running: OnceLock::new(),
+        }
+    }
+
+    fn start(&self) {
+        let mut pending = self.pending.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(stream) = pending.take() {
+            let handle = tokio::spawn(stream).abort_handle();
+            let _ = self.running.set(handle);
+        }
+    }
+}
+
+impl Drop for Task {
+    fn drop(&mut self) {
+        if let Some(handle) = self.running.get() {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use client::ResourceExt as _;
+    use client::client::Body;
+    use http::Request;
+    use http::Response;
+    use k8s::openapi::serde_json as json;
+
+    use super::*;
+
+    fn pod(name: &str) -> json::Value {
+        json::json!({
+            "apiVersion": "v1",
+            "kind": "Pod",
+            "metadata": { "name": name, "namespace": "default", "resourceVersion": "1" }
+        })
+    }
+
+    fn response(body: json::Value) -> Response<Body> {
+        Response::builder()
+            .body(Body::from(json::to_vec(&body).unwrap()))
+            .unwrap()
+    }
+
+    /// Client backed by a mock API server with `web-0` pod in `default` namespace
+    /// Reflector requests (to all namespaces) are only answered when `synced` is set,
+    /// the live API lists `web-1` in place of `web-0` to tell it apart from the cache
+    fn mock_client(synced: bool) -> client::Client {
+        let (service, mut handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        tokio::spawn(async move {
+            let mut watches = Vec::new();
+            while let Some((request, send)) = handle.next_request().await {
+                let watch = request.uri().query().unwrap_or_default().contains("watch=");
+                match request.uri().path() {
+                    "/api/v1/pods" if synced && !watch => {
+                        send.send_response(response(json::json!({
+                            "apiVersion": "v1",
+                            "kind": "PodList",
+                            "metadata": { "resourceVersion": "1" },
+                            "items": [pod("web-0")]
+                        })))
+                    }
+                    "/api/v1/namespaces/default/pods/web-0" => {
+                        send.send_response(response(pod("web-0")))
+                    }
+                    "/api/v1/namespaces/default/pods" => {
+                        send.send_response(response(json::json!({
+                            "apiVersion": "v1",
+                            "kind": "PodList",
+                            "metadata": { "resourceVersion": "2" },
+                            "items": [pod("web-1")]
+                        })))
+                    }
+                    _ => watches.push(send),
+                }
+            }
+        });
+        client::Client::new(service, "default")
+    }
+
+    fn names(pods: &[corev1::Pod]) -> Vec<String> {
+        pods.iter().map(|pod| pod.name_any()).collect()
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_live_api_until_synced() {
+        let cached = Cached::new(mock_client(false)).with_pods();
+        let timeout = cached.wait_until_ready(Duration::from_millis(50)).await;
+        assert!(timeout.is_err());
+
+        let pod = cached.cached_get_pod("web-0", None).await.unwrap();
+        assert!(pod.is_some());
+        let pods = cached.cached_list_pods(None).await.unwrap();
+        assert_eq!(names(&pods), ["web-1"]);
+    }
+
+    #[tokio::test]
+    async fn reads_from_cache_once_synced() {
+        let cached = Cached::new(mock_client(true)).with_pods();
+        cached
+            .wait_until_ready(Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        let pods = cached.cached_list_pods(None).await.unwrap();
+        assert_eq!(names(&pods), ["web-0"]);
+    }
+}
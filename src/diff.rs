@@ -0,0 +1,194 @@
+use k8s::openapi::serde_json as json;
+
+use super::*;
+
+/// Structural difference between two JSON documents
+///
+/// Produced by [`KubeClientExt2::diff_apply`], lists every changed leaf
+/// (object members are compared by key, arrays by index).
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JsonDiff {
+    pub changes: Vec<JsonChange>,
+}
+
+/// Single change within [`JsonDiff`]
+///
+/// `path` is a JSON pointer (RFC 6901), `before` is `None` for added values
+/// and `after` is `None` for removed ones.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonChange {
+    pub path: String,
+    pub before: Option<json::Value>,
+    pub after: Option<json::Value>,
+}
+
+impl JsonDiff {
+    /// Compute difference between `before` (`None` if absent) and `after`
+    ///
+    /// ```
+    /// use kube_client_ext::JsonDiff;
+    /// use k8s_openapi::serde_json::json;
+    ///
+    /// let before = json!({ "spec": { "replicas": 1, "paused": false } });
+    /// let after = json!({ "spec": { "replicas": 3, "minReadySeconds/x": 5 } });
+    /// let diff = JsonDiff::new(Some(&before), &after);
+    ///
+    /// assert_eq!(diff.changed().next().unwrap().path, "/spec/replicas");
+    /// assert_eq!(diff.added().next().unwrap().path, "/spec/minReadySeconds~1x");
+    /// assert_eq!(diff.removed().next().unwrap().path, "/spec/paused");
+    /// ```
+    ///
+    pub fn new(before: Option<&json::Value>, after: &json::Value) -> Self {
+        let mut changes = Vec::new();
+        match before {
+            Some(before) => diff_values(String::new(), before, after, &mut changes),
+            None => changes.push(JsonChange::new(String::new(), None, Some(after))),
+        }
+        Self { changes }
+    }
+
+    /// Return `true` if there are no changes
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Changes adding new values
+    pub fn added(&self) -> impl Iterator<Item = &JsonChange> {
+        self.changes.iter().filter(|change| change.before.is_none())
+    }
+
+    /// Changes removing existing values
+    pub fn removed(&self) -> impl Iterator<Item = &JsonChange> {
+        self.changes.iter().filter(|change| change.after.is_none())
+    }
+
+    /// Changes replacing existing values
+    pub fn changed(&self) -> impl Iterator<Item = &JsonChange> {
+        self.changes
+            .iter()
+            .filter(|change| change.before.is_some() && change.after.is_some())
+    }
+}
+
+impl JsonChange {
+    fn new(path: String, before: Option<&json::Value>, after: Option<&json::Value>) -> Self {
+        Self {
+            path,
+            before: before.cloned(),
+            after: after.cloned(),
+        }
+    }
+}
+
+/// Serialize `object` into JSON without the noisy `metadata.managedFields`
+pub(crate) fn comparable(object: api::DynamicObject) -> client::Result<json::Value> {
+    let mut value = json::to_value(object).map_err(client::Error::SerdeError)?;
+    if let Some(metadata) = value
+        .get_mut("metadata")
+        .and_then(json::Value::as_object_mut)
+    {
+        metadata.remove("managedFields");
+    }
+    Ok(value)
+}
+
+fn diff_values(
+    path: String,
+    before: &json::Value,
+    after: &json::Value,
+    changes: &mut Vec<JsonChange>,
+) {
+    match (before, after) {
+        (json::Value::Object(before), json::Value::Object(after)) => {
+            for (key, old) in before {
+                let path = format!("{path}/{}", escape(key));
+                match after.get(key) {
+                    Some(new) => diff_values(path, old, new, changes),
+                    None => changes.push(JsonChange::new(path, Some(old), None)),
+                }
+            }
+            for (key, new) in after {
+                if !before.contains_key(key) {
+                    let path = format!("{path}/{}", escape(key));
+                    changes.push(JsonChange::new(path, None, Some(new)));
+                }
+            }
+        }
+        (json::Value::Array(before), json::Value::Array(after)) => {
+            for index in 0..before.len().max(after.len()) {
+                let path = format!("{path}/{index}");
+                match (before.get(index), after.get(index)) {
+                    (Some(old), Some(new)) => diff_values(path, old, new, changes),
+                    (old, new) => changes.push(JsonChange::new(path, old, new)),
+                }
+            }
+        }
+        (before, after) if before != after => {
+            changes.push(JsonChange::new(path, Some(before), Some(after)));
+        }
+        _ => {}
+    }
+}
+
+/// Escape JSON pointer reference token
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use json::json;
+
+    use super::*;
+
+    fn change(path: &str, before: Option<json::Value>, after: Option<json::Value>) -> JsonChange {
+        JsonChange {
+            path: path.to_string(),
+            before,
+            after,
+        }
+    }
+
+    #[test]
+    fn arrays_compared_by_index() {
+        let before = json!({ "ports": [{ "port": 80 }, { "port": 443 }], "args": ["-v"] });
+        let after = json!({ "ports": [{ "port": 8080 }], "args": ["-v", "--debug"] });
+        let diff = JsonDiff::new(Some(&before), &after);
+        assert_eq!(
+            diff.changes,
+            [
+                change("/args/1", None, Some(json!("--debug"))),
+                change("/ports/0/port", Some(json!(80)), Some(json!(8080))),
+                change("/ports/1", Some(json!({ "port": 443 })), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn null_is_a_value() {
+        let before = json!({ "replicas": 1, "paused": null, "selector": null });
+        let after = json!({ "replicas": null, "selector": null, "strategy": null });
+        let diff = JsonDiff::new(Some(&before), &after);
+        assert_eq!(
+            diff.changes,
+            [
+                change("/paused", Some(json::Value::Null), None),
+                change("/replicas", Some(json!(1)), Some(json::Value::Null)),
+                change("/strategy", None, Some(json::Value::Null)),
+            ]
+        );
+        assert_eq!(diff.changed().count(), 1);
+        assert_eq!(diff.removed().count(), 1);
+        assert_eq!(diff.added().count(), 1);
+    }
+
+    #[test]
+    fn missing_and_identical_documents() {
+        let object = json!({ "spec": { "replicas": 1 } });
+        let diff = JsonDiff::new(None, &object);
+        assert_eq!(diff.changes, [change("", None, Some(object.clone()))]);
+        assert!(JsonDiff::new(Some(&object), &object).is_empty());
+    }
+}
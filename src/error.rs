@@ -0,0 +1,163 @@
+use std::error::Error;
+use std::fmt;
+
+use super::*;
+
+/// Failure modes of the crate helpers, more precise than plain [`client::Error`]
+///
+/// API errors are classified by their status (`404`, `409`, `403` and
+/// `504 Timeout`), anything else is kept as [`KubeExtError::Kube`].
+/// The original error is available through [`Error::source`].
+///
+/// Converts back into [`client::Error`], so it can be propagated with `?`
+/// from functions returning [`client::Result`].
+///
+#[derive(Debug)]
+pub enum KubeExtError {
+    /// Requested object does not exist
+    NotFound(client::Error),
+    /// Object has been modified concurrently or already exists
+    Conflict(client::Error),
+    /// Caller is not allowed to perform the request
+    Forbidden(client::Error),
+    /// Condition has not been met in time
+    Timeout(client::Error),
+    /// Not a valid resource quantity
+    InvalidQuantity(QuantityParseError),
+    /// Not a valid field or label selector
+    InvalidSelector(SelectorError),
+    /// Object lacks required metadata field (i.e. `name` or `uid`)
+    MissingMetadata(&'static str),
+    /// Argument rejected before sending any request (e.g. unknown pod phase)
+    InvalidArgument(String),
+    /// Any other client error
+    Kube(client::Error),
+}
+
+impl KubeExtError {
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFound(_))
+    }
+
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, Self::Conflict(_))
+    }
+
+    pub fn is_forbidden(&self) -> bool {
+        matches!(self, Self::Forbidden(_))
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout(_))
+    }
+}
+
+impl fmt::Display for KubeExtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(err) => write!(f, "not found: {err}"),
+            Self::Conflict(err) => write!(f, "conflict: {err}"),
+            Self::Forbidden(err) => write!(f, "forbidden: {err}"),
+            Self::Timeout(err) => write!(f, "timed out: {err}"),
+            Self::InvalidQuantity(err) => err.fmt(f),
+            Self::InvalidSelector(err) => err.fmt(f),
+            Self::MissingMetadata(field) => write!(f, "object is missing metadata.{field}"),
+            Self::InvalidArgument(message) => f.write_str(message),
+            Self::Kube(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for KubeExtError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::NotFound(err)
+            | Self::Conflict(err)
+            | Self::Forbidden(err)
+            | Self::Timeout(err)
+            | Self::Kube(err) => Some(err),
+            Self::InvalidQuantity(err) => Some(err),
+            Self::InvalidSelector(err) => Some(err),
+            Self::MissingMetadata(_) | Self::InvalidArgument(_) => None,
+        }
+    }
+}
+
+impl From<client::Error> for KubeExtError {
+    fn from(err: client::Error) -> Self {
+        let client::Error::Api(status) = &err else {
+            return Self::Kube(err);
+        };
+        match status.code {
+            404 => Self::NotFound(err),
+            409 => Self::Conflict(err),
+            403 => Self::Forbidden(err),
+            504 if status.reason == "Timeout" => Self::Timeout(err),
+            _ => Self::Kube(err),
+        }
+    }
+}
+
+impl From<QuantityParseError> for KubeExtError {
+    fn from(err: QuantityParseError) -> Self {
+        Self::InvalidQuantity(err)
+    }
+}
+
+impl From<SelectorError> for KubeExtError {
+    fn from(err: SelectorError) -> Self {
+        Self::InvalidSelector(err)
+    }
+}
+
+impl From<KubeExtError> for client::Error {
+    fn from(err: KubeExtError) -> Self {
+        match err {
+            KubeExtError::NotFound(err)
+            | KubeExtError::Conflict(err)
+            | KubeExtError::Forbidden(err)
+            | KubeExtError::Timeout(err)
+            | KubeExtError::Kube(err) => err,
+            err @ (KubeExtError::InvalidQuantity(_)
+            | KubeExtError::InvalidSelector(_)
+            | KubeExtError::MissingMetadata(_)
+            | KubeExtError::InvalidArgument(_)) => helper::bad_request(&err.to_string()),
+        }
+    }
+}
+
+/// Failure of [`KubeClientExt2::bulk_apply`] that stopped applying objects,
+/// along with the objects applied before it
+///
+/// Converts into the original [`client::Error`], dropping the applied objects.
+///
+#[derive(Debug)]
+pub struct BulkApplyError {
+    /// Error that stopped the bulk apply
+    pub source: client::Error,
+    /// Objects applied before the failure, with their indexes in the list given to `bulk_apply`
+    pub applied: Vec<(usize, api::DynamicObject)>,
+}
+
+impl fmt::Display for BulkApplyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bulk apply stopped after {} applied objects: {}",
+            self.applied.len(),
+            self.source
+        )
+    }
+}
+
+impl Error for BulkApplyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<BulkApplyError> for client::Error {
+    fn from(err: BulkApplyError) -> Self {
+        err.source
+    }
+}
@@ -1,6 +1,11 @@
+use k8s::PodGetExt as _;
+
 use super::*;
 
 pub trait KubeClientExt: Clone {
+    /// Cluster domain used when building in-cluster DNS names
+    const DEFAULT_CLUSTER_DOMAIN: &str = "cluster.local";
+
     fn delete_params(&self) -> api::DeleteParams {
         api::DeleteParams::default().grace_period(0)
     }
@@ -53,6 +58,227 @@ pub trait KubeClientExt: Clone {
             ..api::LogParams::default()
         }
     }
+    /// Namespace used when no namespace is given explicitly
+    /// (as configured by the kubeconfig context)
+    fn default_namespace(&self) -> String {
+        self.default_namespaced_api::<corev1::Pod>()
+            .namespace()
+            .unwrap_or("default")
+            .to_string()
+    }
+
+    /// Effective namespace for `namespace`, falling back to [`Self::default_namespace`]
+    /// the same way namespaced accessors and getters do
+    fn resolve_namespace(&self, namespace: Option<&str>) -> String {
+        namespace.map_or_else(
+            || KubeClientExt::default_namespace(self),
+            ToString::to_string,
+        )
+    }
+
+    /// In-cluster DNS name of a service, i.e. `name.namespace.svc.cluster.local`
+    ///
+    fn service_cluster_dns<'a>(&self, name: &str, namespace: impl Into<Option<&'a str>>) -> String {
+        self.service_cluster_dns_with_domain(name, namespace, Self::DEFAULT_CLUSTER_DOMAIN)
+    }
+
+    /// In-cluster DNS name of a service for a cluster with non-default `domain`
+    ///
+    fn service_cluster_dns_with_domain<'a>(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&'a str>>,
+        domain: &str,
+    ) -> String {
+        let namespace = self.resolve_namespace(namespace.into());
+        format!("{name}.{namespace}.svc.{domain}")
+    }
+
+    /// DNS SRV record name for the named port of a service,
+    /// i.e. `_port-name._tcp.name.namespace.svc.cluster.local`
+    /// Return `None` if the service has no such port
+    ///
+    fn service_port_dns(&self, service: &corev1::Service, port_name: &str) -> Option<String> {
+        let port = service
+            .spec
+            .as_ref()?
+            .ports
+            .as_deref()?
+            .iter()
+            .find(|port| port.name.as_deref() == Some(port_name))?;
+        let protocol = port.protocol.as_deref().unwrap_or("TCP").to_lowercase();
+        let name = service.metadata.name.as_deref()?;
+        let namespace = service.metadata.namespace.as_deref();
+        let service = self.service_cluster_dns(name, namespace);
+        Some(format!("_{port_name}._{protocol}.{service}"))
+    }
+
+    /// Check whether `node` reports `Ready` condition as `True`
+    ///
+    fn node_is_ready(&self, node: &corev1::Node) -> bool {
+        node_condition_is_true(node, "Ready")
+    }
+
+    /// Pressure conditions (`MemoryPressure`, `DiskPressure`, `PIDPressure`)
+    /// currently active on `node`
+    ///
+    fn node_pressure(&self, node: &corev1::Node) -> Vec<String> {
+        ["MemoryPressure", "DiskPressure", "PIDPressure"]
+            .into_iter()
+            .filter(|condition| node_condition_is_true(node, condition))
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Status of the named regular container of the `pod`
+    ///
+    fn container_status<'a>(
+        &self,
+        pod: &'a corev1::Pod,
+        container: &str,
+    ) -> Option<&'a corev1::ContainerStatus> {
+        find_container_status(pod.container_statuses(), container)
+    }
+
+    /// Status of the named init container of the `pod`
+    ///
+    fn init_container_status<'a>(
+        &self,
+        pod: &'a corev1::Pod,
+        container: &str,
+    ) -> Option<&'a corev1::ContainerStatus> {
+        find_container_status(pod.init_container_statuses(), container)
+    }
+
+    /// Current state of the named regular container of the `pod`
+    ///
+    fn container_state(&self, pod: &corev1::Pod, container: &str) -> Option<ContainerStateKind> {
+        self.container_status(pod, container)?
+            .state
+            .as_ref()
+            .and_then(ContainerStateKind::from_state)
+    }
+
+    /// Current state of the named init container of the `pod`
+    ///
+    fn init_container_state(
+        &self,
+        pod: &corev1::Pod,
+        container: &str,
+    ) -> Option<ContainerStateKind> {
+        self.init_container_status(pod, container)?
+            .state
+            .as_ref()
+            .and_then(ContainerStateKind::from_state)
+    }
+
+    /// Name of the CSI driver backing `pv`
+    /// In-tree volume plugins are mapped to the CSI drivers they migrate to
+    /// (e.g. `awsElasticBlockStore` to `ebs.csi.aws.com`)
+    /// Return `None` for other (non-CSI) volume sources
+    ///
+    fn pv_csi_driver(&self, pv: &corev1::PersistentVolume) -> Option<String> {
+        let spec = pv.spec.as_ref()?;
+        if let Some(csi) = &spec.csi {
+            return Some(csi.driver.clone());
+        }
+        let driver = if spec.aws_elastic_block_store.is_some() {
+            "ebs.csi.aws.com"
+        } else if spec.gce_persistent_disk.is_some() {
+            "pd.csi.storage.gke.io"
+        } else if spec.azure_disk.is_some() {
+            "disk.csi.azure.com"
+        } else if spec.azure_file.is_some() {
+            "file.csi.azure.com"
+        } else if spec.cinder.is_some() {
+            "cinder.csi.openstack.org"
+        } else if spec.vsphere_volume.is_some() {
+            "csi.vsphere.vmware.com"
+        } else if spec.portworx_volume.is_some() {
+            "pxd.portworx.com"
+        } else if spec.rbd.is_some() {
+            "rbd.csi.ceph.com"
+        } else {
+            return None;
+        };
+        Some(driver.to_string())
+    }
+
+    /// Effective CPU and memory requests of the `pod` as accounted by the scheduler:
+    /// the larger of the regular containers sum and the init containers maximum
+    /// (taking sidecar init containers into account), plus pod overhead
+    ///
+    fn pod_resource_requests(&self, pod: &corev1::Pod) -> ResourceTotals {
+        ResourceTotals::requests(pod)
+    }
+
+    /// Effective CPU and memory limits of the `pod`, aggregated the same way
+    /// as [`Self::pod_resource_requests`]
+    /// Containers without limits contribute nothing, so the result is a lower bound
+    /// when some containers are unbounded
+    ///
+    fn pod_resource_limits(&self, pod: &corev1::Pod) -> ResourceTotals {
+        ResourceTotals::limits(pod)
+    }
+
+    /// Quality of Service class of the `pod` computed from its container resources,
+    /// rather than read from `status.qosClass` which may not be set
+    ///
+    fn pod_qos_class(&self, pod: &corev1::Pod) -> QosClass {
+        QosClass::of_pod(pod)
+    }
+
+    /// Check whether any container of the `pod`, including init containers,
+    /// is waiting in `CrashLoopBackOff`
+    ///
+    fn pod_is_crashlooping(&self, pod: &corev1::Pod) -> bool {
+        pod.container_statuses()
+            .into_iter()
+            .flatten()
+            .chain(pod.init_container_statuses().into_iter().flatten())
+            .filter_map(|status| status.state.as_ref()?.waiting.as_ref())
+            .any(|waiting| waiting.reason.as_deref() == Some("CrashLoopBackOff"))
+    }
+
+    /// Check whether every custom readiness gate of the `pod` (`spec.readinessGates`)
+    /// has its condition reported as `True` in the pod status
+    /// Pods without readiness gates trivially satisfy them
+    ///
+    fn pod_readiness_gates_satisfied(&self, pod: &corev1::Pod) -> bool {
+        pod.spec
+            .as_ref()
+            .and_then(|spec| spec.readiness_gates.as_deref())
+            .unwrap_or_default()
+            .iter()
+            .all(|gate| pod_condition_is_true(pod, &gate.condition_type))
+    }
+
+    /// Name of the node the `pod` is scheduled to, `None` if it is not scheduled yet
+    ///
+    fn pod_node_name<'a>(&self, pod: &'a corev1::Pod) -> Option<&'a str> {
+        pod.spec.as_ref()?.node_name.as_deref()
+    }
+
+    /// Primary IP address of the node the `pod` is running on, if assigned
+    ///
+    fn pod_host_ip<'a>(&self, pod: &'a corev1::Pod) -> Option<&'a str> {
+        pod.status.as_ref()?.host_ip.as_deref()
+    }
+
+    /// Primary IP address allocated to the `pod`, if assigned
+    ///
+    fn pod_ip<'a>(&self, pod: &'a corev1::Pod) -> Option<&'a str> {
+        pod.status.as_ref()?.pod_ip.as_deref()
+    }
+
+    /// Names of the `ConfigMap`s and `Secret`s the `pod` references through its volumes
+    /// (including projected ones), container environment and image pull secrets
+    /// Nothing is fetched, so referenced objects may not exist
+    ///
+    fn pod_mounts(&self, pod: &corev1::Pod) -> PodMounts {
+        PodMounts::new(pod)
+    }
+
     fn api<K>(&self) -> api::Api<K>
     where
         K: client::Resource,
@@ -69,39 +295,67 @@ pub trait KubeClientExt: Clone {
         <K as client::Resource>::DynamicType: Default;
 
     fn apiservices(&self) -> api::Api<apiregistrationv1::APIService> {
-        self.api()
+        self.cluster_k()
     }
 
     fn clusterroles(&self) -> api::Api<rbacv1::ClusterRole> {
-        self.api()
+        self.cluster_k()
     }
 
     fn clusterrolebindings(&self) -> api::Api<rbacv1::ClusterRoleBinding> {
-        self.api()
+        self.cluster_k()
     }
 
     fn crds(&self) -> api::Api<apiextensionsv1::CustomResourceDefinition> {
-        self.api()
+        self.cluster_k()
+    }
+
+    fn csidrivers(&self) -> api::Api<storagev1::CSIDriver> {
+        self.cluster_k()
     }
 
     fn endpoints<'a>(&self, namespace: impl Into<Option<&'a str>>) -> api::Api<corev1::Endpoints> {
         self.namespaced_k(namespace)
     }
 
+    fn ingressclasses(&self) -> api::Api<networkingv1::IngressClass> {
+        self.cluster_k()
+    }
+
     fn nodes(&self) -> api::Api<corev1::Node> {
-        self.api()
+        self.cluster_k()
+    }
+
+    fn mutatingwebhookconfigurations(
+        &self,
+    ) -> api::Api<admissionregistrationv1::MutatingWebhookConfiguration> {
+        self.cluster_k()
     }
 
     fn namespaces(&self) -> api::Api<corev1::Namespace> {
-        self.api()
+        self.cluster_k()
     }
 
     fn persistentvolumes(&self) -> api::Api<corev1::PersistentVolume> {
-        self.api()
+        self.cluster_k()
+    }
+
+    fn priorityclasses(&self) -> api::Api<schedulingv1::PriorityClass> {
+        self.cluster_k()
+    }
+
+    fn runtimeclasses(&self) -> api::Api<nodev1::RuntimeClass> {
+        self.cluster_k()
     }
 
     fn storageclasses(&self) -> api::Api<storagev1::StorageClass> {
-        self.api()
+        self.cluster_k()
+    }
+
+    fn validatingwebhookconfigurations(
+        &self,
+    ) -> api::Api<admissionregistrationv1::ValidatingWebhookConfiguration> {
+        self.cluster_k()
     }
 
     fn configmaps<'a>(&self, namespace: impl Into<Option<&'a str>>) -> api::Api<corev1::ConfigMap> {
@@ -119,6 +373,10 @@ pub trait KubeClientExt: Clone {
         self.namespaced_k(namespace)
     }
 
+    fn events<'a>(&self, namespace: impl Into<Option<&'a str>>) -> api::Api<corev1::Event> {
+        self.namespaced_k(namespace)
+    }
+
     fn horizontalpodautoscalers<'a>(
         &self,
         namespace: impl Into<Option<&'a str>>,
@@ -126,6 +384,13 @@ pub trait KubeClientExt: Clone {
         self.namespaced_k(namespace)
     }
 
+    fn ingresses<'a>(
+        &self,
+        namespace: impl Into<Option<&'a str>>,
+    ) -> api::Api<networkingv1::Ingress> {
+        self.namespaced_k(namespace)
+    }
+
     fn jobs<'a>(&self, namespace: impl Into<Option<&'a str>>) -> api::Api<batchv1::Job> {
         self.namespaced_k(namespace)
     }
@@ -134,6 +399,10 @@ pub trait KubeClientExt: Clone {
         self.namespaced_k(namespace)
     }
 
+    fn leases<'a>(&self, namespace: impl Into<Option<&'a str>>) -> api::Api<coordinationv1::Lease> {
+        self.namespaced_k(namespace)
+    }
+
     fn persistentvolumeclaims<'a>(
         &self,
         namespace: impl Into<Option<&'a str>>,
@@ -148,6 +417,13 @@ pub trait KubeClientExt: Clone {
         self.namespaced_k(namespace)
     }
 
+    fn replicationcontrollers<'a>(
+        &self,
+        namespace: impl Into<Option<&'a str>>,
+    ) -> api::Api<corev1::ReplicationController> {
+        self.namespaced_k(namespace)
+    }
+
     fn roles<'a>(&self, namespace: impl Into<Option<&'a str>>) -> api::Api<rbacv1::Role> {
         self.namespaced_k(namespace)
     }
@@ -196,6 +472,14 @@ pub trait KubeClientExt: Clone {
             self.default_namespaced_api()
         }
     }
+
+    fn cluster_k<K>(&self) -> api::Api<K>
+    where
+        K: client::Resource<Scope = k8s::openapi::ClusterResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        self.api()
+    }
 }
 
 impl KubeClientExt for client::Client {
@@ -223,3 +507,28 @@ impl KubeClientExt for client::Client {
         api::Api::<K>::namespaced(self.clone(), namespace)
     }
 }
+
+fn node_condition_is_true(node: &corev1::Node, r#type: &str) -> bool {
+    node.status
+        .as_ref()
+        .and_then(|status| status.conditions.as_deref())
+        .unwrap_or_default()
+        .iter()
+        .any(|condition| condition.type_ == r#type && condition.status == "True")
+}
+
+fn pod_condition_is_true(pod: &corev1::Pod, r#type: &str) -> bool {
+    pod.status
+        .as_ref()
+        .and_then(|status| status.conditions.as_deref())
+        .unwrap_or_default()
+        .iter()
+        .any(|condition| condition.type_ == r#type && condition.status == "True")
+}
+
+fn find_container_status<'a>(
+    statuses: Option<&'a [corev1::ContainerStatus]>,
+    container: &str,
+) -> Option<&'a corev1::ContainerStatus> {
+    statuses?.iter().find(|status| status.name == container)
+}
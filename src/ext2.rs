@@ -1,14 +1,57 @@
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::fmt;
+use std::time::Duration;
 
+use base64::Engine as _;
 use client::ResourceExt as _;
+use client::discovery;
+use futures::AsyncBufReadExt as _;
+use futures::FutureExt as _;
+use futures::StreamExt as _;
+use futures::TryStreamExt as _;
+use futures::future;
+use futures::stream::BoxStream;
+use k8s::ConfigMapExt as _;
 use k8s::DeploymentGetExt as _;
 use k8s::OwnerReferenceExt as _;
 use k8s::ReplicaSetGetExt as _;
+use k8s::SecretExt as _;
+use k8s::ServiceGetExt as _;
 use k8s::StatefulSetGetExt as _;
+use k8s::openapi::serde_json as json;
 
 use super::*;
 
+/// Maximum number of concurrent requests issued by bulk helpers
+const CONCURRENCY: usize = 8;
+
+/// Interval between polls of helpers waiting for a condition
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum time [`KubeClientExt2::bulk_apply`] waits for applied CRDs to become `Established`
+const CRD_ESTABLISHED_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Valid values of pod `status.phase`
+const POD_PHASES: [&str; 5] = ["Pending", "Running", "Succeeded", "Failed", "Unknown"];
+
+/// Event reasons reported by the horizontal pod autoscaler controller about its scaling decisions
+const HPA_SCALING_REASONS: [&str; 10] = [
+    "SuccessfulRescale",
+    "FailedRescale",
+    "FailedGetScale",
+    "FailedComputeMetricsReplicas",
+    "FailedGetResourceMetric",
+    "FailedGetContainerResourceMetric",
+    "FailedGetPodsMetric",
+    "FailedGetObjectMetric",
+    "FailedGetExternalMetric",
+    "FailedUpdateStatus",
+];
+
 /// Async extentions to `kube::Client`
 ///
 #[async_trait::async_trait]
@@ -21,7 +64,7 @@ pub trait KubeClientExt2: KubeClientExt {
         name: &str,
         namespace: impl Into<Option<&str>> + Send,
     ) -> client::Result<Option<corev1::ConfigMap>> {
-        self.configmaps(namespace).get_opt(name).await
+        self.get_k_opt(name, namespace).await
     }
 
     /// Get named configmap from a given (or default) namespace
@@ -31,7 +74,67 @@ pub trait KubeClientExt2: KubeClientExt {
         name: &str,
         namespace: impl Into<Option<&str>> + Send,
     ) -> client::Result<corev1::ConfigMap> {
-        self.configmaps(namespace).get(name).await
+        self.get_k(name, namespace).await
+    }
+
+    /// Merge `data` into named configmap in a given (or default) namespace
+    /// using strategic merge patch, so keys not present in `data` are preserved
+    /// The configmap is created if it does not exist yet
+    ///
+    async fn merge_configmap(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+        data: BTreeMap<String, String>,
+    ) -> client::Result<corev1::ConfigMap> {
+        let configmaps = self.configmaps(namespace);
+        let patch = api::Patch::Strategic(json::json!({ "data": &data }));
+        match configmaps.patch(name, &self.patch_params(), &patch).await {
+            Err(client::Error::Api(status)) if status.is_not_found() => {
+                let configmap = corev1::ConfigMap::new(name).data(data);
+                configmaps.create(&self.post_params(), &configmap).await
+            }
+            other => other,
+        }
+    }
+
+    /// Copy named configmap from `src_namespace` to `dst_namespace`, optionally renaming it
+    ///
+    /// `data` and `binaryData` are copied verbatim, while only name, labels and
+    /// annotations (except `last-applied-configuration`) of the source metadata are kept,
+    /// so the copy has its own identity and no owners.
+    ///
+    async fn copy_configmap(
+        &self,
+        name: &str,
+        src_namespace: impl Into<Option<&str>> + Send,
+        dst_namespace: impl Into<Option<&str>> + Send,
+        new_name: Option<&str>,
+    ) -> client::Result<corev1::ConfigMap> {
+        let configmap = self.get_configmap(name, src_namespace).await?;
+        let configmap = copied_configmap(configmap, new_name.unwrap_or(name));
+        let pp = self.post_params();
+        self.configmaps(dst_namespace).create(&pp, &configmap).await
+    }
+
+    /// Create or update (using server-side apply) a copy of named configmap from `src_namespace`
+    /// in `dst_namespace`, optionally renaming it (see [`Self::copy_configmap`])
+    ///
+    async fn apply_copy_configmap(
+        &self,
+        name: &str,
+        src_namespace: impl Into<Option<&str>> + Send,
+        dst_namespace: impl Into<Option<&str>> + Send,
+        new_name: Option<&str>,
+        manager: &str,
+    ) -> client::Result<corev1::ConfigMap> {
+        let configmap = self.get_configmap(name, src_namespace).await?;
+        let name = new_name.unwrap_or(name);
+        let configmap = copied_configmap(configmap, name);
+        let pp = self.patch_params_with_manager(manager);
+        self.configmaps(dst_namespace)
+            .patch(name, &pp, &api::Patch::Apply(&configmap))
+            .await
     }
 
     /// Get named secret from a given (or default) namespace
@@ -42,7 +145,7 @@ pub trait KubeClientExt2: KubeClientExt {
         name: &str,
         namespace: impl Into<Option<&str>> + Send,
     ) -> client::Result<Option<corev1::Secret>> {
-        self.secrets(namespace).get_opt(name).await
+        self.get_k_opt(name, namespace).await
     }
 
     /// Get named secret from a given (or default) namespace
@@ -52,291 +155,3057 @@ pub trait KubeClientExt2: KubeClientExt {
         name: &str,
         namespace: impl Into<Option<&str>> + Send,
     ) -> client::Result<corev1::Secret> {
-        self.secrets(namespace).get(name).await
+        self.get_k(name, namespace).await
     }
 
-    /// Get named deployment from a given (or default) namespace
-    /// Return `None` if not found
+    /// Merge `data` of named configmap with decoded values of named secret
+    /// from a given (or default) namespace, the way `envFrom` layers them
+    /// Secret values win on key conflict, a missing configmap or secret contributes nothing
+    /// and binary secret values are decoded lossily as UTF-8
     ///
-    async fn get_deployment_opt(
+    async fn get_merged_env(
         &self,
-        name: &str,
+        cm_name: &str,
+        secret_name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Option<appsv1::Deployment>> {
-        self.deployments(namespace).get_opt(name).await
+    ) -> client::Result<BTreeMap<String, String>> {
+        let namespace = namespace.into();
+        let (configmap, secret) = futures::try_join!(
+            self.get_configmap_opt(cm_name, namespace),
+            self.get_secret_opt(secret_name, namespace),
+        )?;
+        let mut env = configmap
+            .and_then(|configmap| configmap.data)
+            .unwrap_or_default();
+        env.extend(secret.map(secret_text_data).unwrap_or_default());
+        Ok(env)
     }
 
-    /// Get named deployment from a given (or default) namespace
+    /// Reconstruct effective environment of the named (regular or init) container of the `pod`
+    /// fetching configmaps and secrets referenced by its `envFrom` and `env` entries
     ///
-    async fn get_deployment(
+    /// `envFrom` sources are applied in order (with their `prefix`) and then overridden
+    /// by `env` entries, like the kubelet does. Values from `configMapKeyRef` and
+    /// `secretKeyRef` are resolved, while `fieldRef` and `resourceFieldRef` are
+    /// represented symbolically as `<fieldRef:FIELD_PATH>` and `<resourceFieldRef:RESOURCE>`.
+    /// Missing configmaps, secrets or keys contribute nothing, other `valueFrom`
+    /// sources are skipped and `$(VAR)` references are not expanded.
+    ///
+    async fn pod_effective_env(
         &self,
-        name: &str,
-        namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<appsv1::Deployment> {
-        self.deployments(namespace).get(name).await
+        pod: &corev1::Pod,
+        container_name: &str,
+    ) -> client::Result<BTreeMap<String, String>> {
+        let Some(container) = pod_container(pod, container_name) else {
+            let message = format!(
+                "Container {container_name} not found in pod {}",
+                pod.name_any()
+            );
+            return Err(helper::bad_request(&message));
+        };
+        let namespace = pod.namespace();
+        let (configmap_names, secret_names) = env_sources(container);
+
+        let mut configmaps = BTreeMap::new();
+        for name in configmap_names {
+            let data = self
+                .get_configmap_opt(name, namespace.as_deref())
+                .await?
+                .and_then(|configmap| configmap.data)
+                .unwrap_or_default();
+            configmaps.insert(name, data);
+        }
+
+        let mut secrets = BTreeMap::new();
+        for name in secret_names {
+            let data = self
+                .get_secret_opt(name, namespace.as_deref())
+                .await?
+                .map(secret_text_data)
+                .unwrap_or_default();
+            secrets.insert(name, data);
+        }
+
+        Ok(container_env(container, &configmaps, &secrets))
     }
 
-    /// Get named statefulset from a given (or default) namespace
-    /// Return `None` if not found
+    /// Create `kubernetes.io/tls` secret with given PEM encoded certificate and key
+    /// in a given (or default) namespace
     ///
-    async fn get_statefulset_opt(
+    async fn create_tls_secret(
         &self,
         name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Option<appsv1::StatefulSet>> {
-        self.statefulsets(namespace).get_opt(name).await
+        cert_pem: &[u8],
+        key_pem: &[u8],
+    ) -> client::Result<corev1::Secret> {
+        let secret = tls_secret(name, cert_pem, key_pem)?;
+        let pp = self.post_params();
+        self.secrets(namespace).create(&pp, &secret).await
     }
 
-    /// Get named statefulset from a given (or default) namespace
+    /// Create or update (using server-side apply) `kubernetes.io/tls` secret
+    /// with given PEM encoded certificate and key in a given (or default) namespace
     ///
-    async fn get_statefulset(
+    async fn apply_tls_secret(
         &self,
         name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<appsv1::StatefulSet> {
-        self.statefulsets(namespace).get(name).await
+        cert_pem: &[u8],
+        key_pem: &[u8],
+        manager: &str,
+    ) -> client::Result<corev1::Secret> {
+        let secret = tls_secret(name, cert_pem, key_pem)?;
+        let pp = self.patch_params_with_manager(manager);
+        self.secrets(namespace)
+            .patch(name, &pp, &api::Patch::Apply(&secret))
+            .await
     }
 
-    /// Get named api service
-    /// Return `None` if not found
+    /// Create `Opaque` secret from plain text `data` in a given (or default) namespace
+    /// Values are sent in `stringData` and encoded by the API server,
+    /// i.e. they travel in plain text form (protected by TLS only)
     ///
-    async fn get_apiservice_opt(
+    async fn create_opaque_secret(
         &self,
         name: &str,
-    ) -> client::Result<Option<apiregistrationv1::APIService>> {
-        self.apiservices().get_opt(name).await
+        namespace: impl Into<Option<&str>> + Send,
+        data: BTreeMap<String, String>,
+    ) -> client::Result<corev1::Secret> {
+        let secret = opaque_secret(name, data)?;
+        let pp = self.post_params();
+        self.secrets(namespace).create(&pp, &secret).await
     }
 
-    /// Get named api service
+    /// Create or update (using server-side apply) `Opaque` secret from plain text `data`
+    /// in a given (or default) namespace
+    /// Values are sent in `stringData` and encoded by the API server,
+    /// i.e. they travel in plain text form (protected by TLS only)
     ///
-    async fn get_apiservice(&self, name: &str) -> client::Result<apiregistrationv1::APIService> {
-        self.apiservices().get(name).await
+    async fn apply_opaque_secret(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+        data: BTreeMap<String, String>,
+        manager: &str,
+    ) -> client::Result<corev1::Secret> {
+        let secret = opaque_secret(name, data)?;
+        let pp = self.patch_params_with_manager(manager);
+        self.secrets(namespace)
+            .patch(name, &pp, &api::Patch::Apply(&secret))
+            .await
     }
 
-    /// Get named CRD
-    /// Return `None` if not found
+    /// Copy named secret from `src_namespace` to `dst_namespace`, optionally renaming it
     ///
-    async fn get_crd_opt(
+    /// Only name, labels and annotations (except `last-applied-configuration`) of
+    /// the source metadata are kept, so the copy has its own identity and no owners.
+    ///
+    async fn copy_secret(
         &self,
         name: &str,
-    ) -> client::Result<Option<apiextensionsv1::CustomResourceDefinition>> {
-        self.crds().get_opt(name).await
+        src_namespace: impl Into<Option<&str>> + Send,
+        dst_namespace: impl Into<Option<&str>> + Send,
+        new_name: Option<&str>,
+    ) -> client::Result<corev1::Secret> {
+        let secret = self.get_secret(name, src_namespace).await?;
+        let secret = copied_secret(secret, new_name.unwrap_or(name));
+        let pp = self.post_params();
+        self.secrets(dst_namespace).create(&pp, &secret).await
     }
 
-    /// Get named CRD
+    /// Create or update (using server-side apply) a copy of named secret from `src_namespace`
+    /// in `dst_namespace`, optionally renaming it (see [`Self::copy_secret`])
     ///
-    async fn get_crd(
+    async fn apply_copy_secret(
         &self,
         name: &str,
-    ) -> client::Result<apiextensionsv1::CustomResourceDefinition> {
-        self.crds().get(name).await
+        src_namespace: impl Into<Option<&str>> + Send,
+        dst_namespace: impl Into<Option<&str>> + Send,
+        new_name: Option<&str>,
+        manager: &str,
+    ) -> client::Result<corev1::Secret> {
+        let secret = self.get_secret(name, src_namespace).await?;
+        let name = new_name.unwrap_or(name);
+        let secret = copied_secret(secret, name);
+        let pp = self.patch_params_with_manager(manager);
+        self.secrets(dst_namespace)
+            .patch(name, &pp, &api::Patch::Apply(&secret))
+            .await
     }
 
-    /// Get owner object from `ownerReference` assuming it is of kind `K`
+    /// Create `kubernetes.io/dockerconfigjson` image pull secret with credentials
+    /// for a single `registry` in a given (or default) namespace
     ///
-    async fn get_owner_k<O, K>(&self, o: &O) -> client::Result<Option<K>>
-    where
-        O: client::ResourceExt + Sync,
-        K: Clone
-            + fmt::Debug
-            + k8s::openapi::serde::de::DeserializeOwned
-            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
-        <K as client::Resource>::DynamicType: Default,
-    {
-        let dynamic_default = K::DynamicType::default();
-        let kind = K::kind(&dynamic_default);
-        let namespace = o.namespace();
-        if let Some(name) = o
-            .owner_references()
-            .iter()
-            .find(|owner| owner.kind == kind)
-            .map(|owner| &owner.name)
-        {
-            self.namespaced_k(namespace.as_deref()).get_opt(name).await
-        } else {
-            Ok(None)
-        }
+    async fn create_docker_config_secret(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+        registry: &str,
+        username: &str,
+        password: &str,
+    ) -> client::Result<corev1::Secret> {
+        let config = docker_config_json([(registry, username, password)]);
+        let secret = corev1::Secret::docker_config_json_text(name, config);
+        let pp = self.post_params();
+        self.secrets(namespace).create(&pp, &secret).await
     }
 
-    /// List all `Pod`s  in a given (or default) namespace
+    /// Create `kubernetes.io/dockerconfigjson` image pull secret with credentials
+    /// for several registries in a given (or default) namespace
+    /// `registries` maps registry server to its `(username, password)` pair
     ///
-    async fn list_pods(
+    async fn create_docker_config_secret_multi(
         &self,
+        name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Vec<corev1::Pod>> {
-        self.list_k(namespace).await
+        registries: &BTreeMap<String, (String, String)>,
+    ) -> client::Result<corev1::Secret> {
+        let auths = registries.iter().map(|(registry, (username, password))| {
+            (registry.as_str(), username.as_str(), password.as_str())
+        });
+        let config = docker_config_json(auths);
+        let secret = corev1::Secret::docker_config_json_text(name, config);
+        let pp = self.post_params();
+        self.secrets(namespace).create(&pp, &secret).await
     }
 
-    /// List all `Deployment`s in a given (or default) namespace
+    /// Get named deployment from a given (or default) namespace
+    /// Return `None` if not found
     ///
-    async fn list_deployments(
+    async fn get_deployment_opt(
         &self,
+        name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Vec<appsv1::Deployment>> {
-        self.list_k(namespace).await
+    ) -> client::Result<Option<appsv1::Deployment>> {
+        self.get_k_opt(name, namespace).await
     }
 
-    /// List all `ReplicaSets` in a given (or default) namespace
+    /// Get named deployment from a given (or default) namespace
     ///
-    async fn list_replicasets(
+    async fn get_deployment(
         &self,
+        name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Vec<appsv1::ReplicaSet>> {
-        self.list_k(namespace).await
+    ) -> client::Result<appsv1::Deployment> {
+        self.get_k(name, namespace).await
     }
 
-    /// List all `Job`s in a given (or default) namespace
+    /// Get image of a `container` (or of the first container if not given)
+    /// in the pod template of named deployment from a given (or default) namespace
+    /// Return `BadRequest` error if there is no such container
     ///
-    async fn list_jobs(
+    async fn get_deployment_image(
         &self,
+        name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Vec<batchv1::Job>> {
-        self.list_k(namespace).await
+        container: Option<&str>,
+    ) -> client::Result<Option<String>> {
+        let deployment = self.get_deployment(name, namespace).await?;
+        let container = deployment_container(&deployment, container)?;
+        Ok(container.image.clone())
     }
 
-    /// List all `CronJob`s in a given (or default) namespace
+    /// Set image of a `container` (or of the first container if not given)
+    /// in the pod template of named deployment in a given (or default) namespace,
+    /// like `kubectl set image` does
+    /// Return `BadRequest` error if there is no such container
     ///
-    async fn list_cronjobs(
+    async fn set_deployment_image(
         &self,
+        name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Vec<batchv1::CronJob>> {
-        self.list_k(namespace).await
+        container: Option<&str>,
+        image: &str,
+    ) -> client::Result<appsv1::Deployment> {
+        let deployments = self.deployments(namespace);
+        let deployment = deployments.get(name).await?;
+        let container = deployment_container(&deployment, container)?;
+        let patch = json::json!({
+            "spec": {
+                "template": {
+                    "spec": {
+                        "containers": [{ "name": container.name, "image": image }]
+                    }
+                }
+            }
+        });
+        deployments
+            .patch(name, &self.patch_params(), &api::Patch::Strategic(patch))
+            .await
     }
 
-    /// List all `Secret`s in a given (or default) namespace
+    /// Set environment variable `key` to `value` for a `container` (or the first container
+    /// if not given) in the pod template of named deployment in a given (or default) namespace
+    /// An existing variable with the same name is updated in place (replacing its `valueFrom`)
+    /// Return `BadRequest` error if there is no such container
     ///
-    async fn list_secrets(
+    async fn set_deployment_env(
         &self,
+        name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Vec<corev1::Secret>> {
-        self.list_k(namespace).await
+        container: Option<&str>,
+        key: &str,
+        value: &str,
+    ) -> client::Result<appsv1::Deployment> {
+        let var = json::json!({ "name": key, "value": value, "valueFrom": null });
+        let deployments = self.deployments(namespace);
+        patch_container_env(&deployments, name, container, var, &self.patch_params()).await
     }
 
-    /// List all `Service`s in a given (or default) namespace
+    /// Remove environment variable `key` from a `container` (or the first container
+    /// if not given) in the pod template of named deployment in a given (or default) namespace
+    /// Return `BadRequest` error if there is no such container
     ///
-    async fn list_services(
+    async fn unset_deployment_env(
         &self,
+        name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Vec<corev1::Service>> {
-        self.list_k(namespace).await
+        container: Option<&str>,
+        key: &str,
+    ) -> client::Result<appsv1::Deployment> {
+        let var = json::json!({ "name": key, "$patch": "delete" });
+        let deployments = self.deployments(namespace);
+        patch_container_env(&deployments, name, container, var, &self.patch_params()).await
     }
 
-    /// List all `StatefulSet`s in a given (or default) namespace
+    /// Add `toleration` to the pod template of named deployment in a given (or default)
+    /// namespace, replacing an existing toleration with the same key, operator and effect
     ///
-    async fn list_statefulsets(
+    /// Tolerations are replaced as a whole list, so the patch is made conditional on
+    /// the resource version that has been read, failing with `Conflict` on concurrent updates.
+    ///
+    async fn add_deployment_toleration(
         &self,
+        name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Vec<appsv1::StatefulSet>> {
-        self.list_k(namespace).await
+        toleration: corev1::Toleration,
+    ) -> client::Result<appsv1::Deployment> {
+        let deployments = self.deployments(namespace);
+        let deployment = deployments.get(name).await?;
+        let operator = |toleration: &corev1::Toleration| {
+            toleration
+                .operator
+                .clone()
+                .unwrap_or_else(|| "Equal".to_string())
+        };
+        let mut tolerations = deployment
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.template.spec.as_ref())
+            .and_then(|spec| spec.tolerations.clone())
+            .unwrap_or_default();
+        tolerations.retain(|existing| {
+            existing.key != toleration.key
+                || operator(existing) != operator(&toleration)
+                || existing.effect != toleration.effect
+        });
+        tolerations.push(toleration);
+        let patch = json::json!({
+            "metadata": { "resourceVersion": deployment.resource_version() },
+            "spec": { "template": { "spec": { "tolerations": tolerations } } }
+        });
+        deployments
+            .patch(name, &self.patch_params(), &api::Patch::Merge(patch))
+            .await
     }
 
-    /// List all `ConfigMap`s in a given (or default) namespace
+    /// Get named replication controller from a given (or default) namespace
+    /// Return `None` if not found
     ///
-    async fn list_configmaps(
+    async fn get_replicationcontroller_opt(
         &self,
+        name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Vec<corev1::ConfigMap>> {
-        self.list_k(namespace).await
+    ) -> client::Result<Option<corev1::ReplicationController>> {
+        self.get_k_opt(name, namespace).await
     }
 
-    /// List all `ServiceAccount`s in a given (or default) namespace
+    /// Get named replication controller from a given (or default) namespace
     ///
-    async fn list_serviceaccounts(
+    async fn get_replicationcontroller(
         &self,
+        name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Vec<corev1::ServiceAccount>> {
-        self.list_k(namespace).await
+    ) -> client::Result<corev1::ReplicationController> {
+        self.get_k(name, namespace).await
     }
 
-    /// List namespaced objects of kind `K` in a given (or default) namespace
+    /// Get named statefulset from a given (or default) namespace
+    /// Return `None` if not found
     ///
-    async fn list_k<K>(&self, namespace: impl Into<Option<&str>> + Send) -> client::Result<Vec<K>>
-    where
-        K: Clone
-            + fmt::Debug
-            + k8s::openapi::serde::de::DeserializeOwned
-            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
-        <K as client::Resource>::DynamicType: Default,
-    {
-        let lp = self.list_params();
-        self.namespaced_k(namespace)
-            .list(&lp)
-            .await
-            .map(|list| list.items)
+    async fn get_statefulset_opt(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Option<appsv1::StatefulSet>> {
+        self.get_k_opt(name, namespace).await
     }
 
-    /// Get all the pods associated with the deployment
-    /// The logic is based on what `kubectl describe` does
+    /// Get named statefulset from a given (or default) namespace
     ///
-    async fn get_pods_by_deployment_name(
+    async fn get_statefulset(
         &self,
         name: &str,
         namespace: impl Into<Option<&str>> + Send,
-    ) -> client::Result<Option<Vec<corev1::Pod>>> {
-        // Get the deployment
-        let Some(deployment) = self.get_deployment_opt(name, namespace).await? else {
-            return Ok(None);
-        };
-        self.get_pods_by_deployment(&deployment).await
+    ) -> client::Result<appsv1::StatefulSet> {
+        self.get_k(name, namespace).await
     }
 
-    /// Get all the pods associated with the `deployment`
-    /// The logic is based on what `kubectl describe` does
+    /// Get named job from a given (or default) namespace
+    /// Return `None` if not found
     ///
-    async fn get_pods_by_deployment(
+    async fn get_job_opt(
         &self,
-        deployment: &appsv1::Deployment,
-    ) -> client::Result<Option<Vec<corev1::Pod>>> {
-        let namespace = deployment.namespace();
-        // Get all its replicas
-        let mut replicasets = self
-            .list_replicasets(namespace.as_deref())
-            .await?
-            .into_iter()
-            .filter(|rs| rs.is_controlled_by(deployment))
-            .collect::<Vec<_>>();
-
-        // Find the `NewReplicaSet`
-        replicasets.sort_by_key(|rs| rs.creation_timestamp());
-        let Some(new) = replicasets
-            .iter()
-            .find(|rs| match_template_spec_no_hash(rs, deployment))
-        else {
-            return Ok(None);
-        };
-
-        // Find all the Pods controlled by this ReplicaSet
-        let pods = self
-            .list_pods(namespace.as_deref())
-            .await?
-            .into_iter()
-            .filter(|pod| pod.is_controlled_by(new))
-            .collect();
-
-        Ok(Some(pods))
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Option<batchv1::Job>> {
+        self.get_k_opt(name, namespace).await
     }
 
-    /// Get all the pods controlled by a given statefulset
-    async fn get_pods_by_statefulset(
+    /// Get named job from a given (or default) namespace
+    ///
+    async fn get_job(
         &self,
-        statefulset: &appsv1::StatefulSet,
-    ) -> client::Result<Option<Vec<corev1::Pod>>> {
-        let namespace = statefulset.namespace();
-        let pods = if let Some(revision) = statefulset.current_revision() {
-            let controller_revision = format!(
-                "{}={}",
-                k8s::label::CONTROLLER_REVISION_HASH_LABEL_KEY,
-                revision
-            );
-            let lp = self.list_params().labels(&controller_revision);
-            self.pods(namespace.as_deref()).list(&lp).await?.items
-        } else {
-            vec![]
-        };
-        Ok(Some(pods))
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<batchv1::Job> {
+        self.get_k(name, namespace).await
     }
 
-    async fn get_pods_by_statefulset_name(
+    /// Summarize status of named job from a given (or default) namespace
+    /// Return `None` if not found
+    ///
+    async fn job_stats_opt(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Option<JobStats>> {
+        self.get_job_opt(name, namespace)
+            .await
+            .map(|job| job.as_ref().map(JobStats::new))
+    }
+
+    /// Summarize status of named job from a given (or default) namespace
+    ///
+    async fn job_stats(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<JobStats> {
+        self.get_job(name, namespace)
+            .await
+            .map(|job| JobStats::new(&job))
+    }
+
+    /// Get named api service
+    /// Return `None` if not found
+    ///
+    async fn get_apiservice_opt(
+        &self,
+        name: &str,
+    ) -> client::Result<Option<apiregistrationv1::APIService>> {
+        self.get_cluster_k_opt(name).await
+    }
+
+    /// Get named api service
+    ///
+    async fn get_apiservice(&self, name: &str) -> client::Result<apiregistrationv1::APIService> {
+        self.get_cluster_k(name).await
+    }
+
+    /// Get named CRD
+    /// Return `None` if not found
+    ///
+    async fn get_crd_opt(
+        &self,
+        name: &str,
+    ) -> client::Result<Option<apiextensionsv1::CustomResourceDefinition>> {
+        self.get_cluster_k_opt(name).await
+    }
+
+    /// Get named CRD
+    ///
+    async fn get_crd(
+        &self,
+        name: &str,
+    ) -> client::Result<apiextensionsv1::CustomResourceDefinition> {
+        self.get_cluster_k(name).await
+    }
+
+    /// List instances of the custom resource defined by `crd`, in a given namespace
+    /// or across all namespaces when `namespace` is `None` (ignored for cluster scoped resources)
+    ///
+    /// Instances are fetched in the storage version if it is served,
+    /// or in the first served version otherwise.
+    ///
+    async fn get_crd_instances(
+        &self,
+        crd: &apiextensionsv1::CustomResourceDefinition,
+        namespace: Option<&str>,
+    ) -> client::Result<Vec<api::DynamicObject>> {
+        let api = crd_api(&client_of(self), crd, namespace)?;
+        api.list(&self.list_params()).await.map(|list| list.items)
+    }
+
+    /// Count instances of the custom resource defined by named CRD across all namespaces,
+    /// e.g. to tell how many objects deleting the CRD would remove
+    /// The count is approximate the same way as in [`Self::count_k`]
+    ///
+    async fn count_crd_instances(&self, name: &str) -> client::Result<usize> {
+        let crd = self.get_crd(name).await?;
+        let api = crd_api(&client_of(self), &crd, None)?;
+        count_objects(&api, &self.list_params()).await
+    }
+
+    /// Get named priority class
+    /// Return `None` if not found
+    ///
+    async fn get_priorityclass_opt(
+        &self,
+        name: &str,
+    ) -> client::Result<Option<schedulingv1::PriorityClass>> {
+        self.get_cluster_k_opt(name).await
+    }
+
+    /// Get named priority class
+    ///
+    async fn get_priorityclass(&self, name: &str) -> client::Result<schedulingv1::PriorityClass> {
+        self.get_cluster_k(name).await
+    }
+
+    /// List all `PriorityClass`es
+    ///
+    async fn list_priorityclasses(&self) -> client::Result<Vec<schedulingv1::PriorityClass>> {
+        self.list_cluster_k().await
+    }
+
+    /// Resolve priority of the `pod` from its `priorityClassName`, falling back to
+    /// the priority class marked as `globalDefault` when the pod does not name one
+    /// Return `None` if the named priority class does not exist, or there is no global default
+    ///
+    async fn pod_priority_value(&self, pod: &corev1::Pod) -> client::Result<Option<i32>> {
+        let class = pod
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.priority_class_name.as_deref())
+            .filter(|name| !name.is_empty());
+        let value = if let Some(class) = class {
+            self.get_priorityclass_opt(class)
+                .await?
+                .map(|class| class.value)
+        } else {
+            self.list_priorityclasses()
+                .await?
+                .into_iter()
+                .find(|class| class.global_default == Some(true))
+                .map(|class| class.value)
+        };
+        Ok(value)
+    }
+
+    /// Get named runtime class
+    /// Return `None` if not found
+    ///
+    async fn get_runtimeclass_opt(
+        &self,
+        name: &str,
+    ) -> client::Result<Option<nodev1::RuntimeClass>> {
+        self.get_cluster_k_opt(name).await
+    }
+
+    /// Get named runtime class
+    ///
+    async fn get_runtimeclass(&self, name: &str) -> client::Result<nodev1::RuntimeClass> {
+        self.get_cluster_k(name).await
+    }
+
+    /// List all `RuntimeClass`es
+    ///
+    async fn list_runtimeclasses(&self) -> client::Result<Vec<nodev1::RuntimeClass>> {
+        self.list_cluster_k().await
+    }
+
+    /// Get named ingress class
+    /// Return `None` if not found
+    ///
+    async fn get_ingressclass_opt(
+        &self,
+        name: &str,
+    ) -> client::Result<Option<networkingv1::IngressClass>> {
+        self.get_cluster_k_opt(name).await
+    }
+
+    /// Get named ingress class
+    ///
+    async fn get_ingressclass(&self, name: &str) -> client::Result<networkingv1::IngressClass> {
+        self.get_cluster_k(name).await
+    }
+
+    /// List all `IngressClass`es
+    ///
+    async fn list_ingressclasses(&self) -> client::Result<Vec<networkingv1::IngressClass>> {
+        self.list_cluster_k().await
+    }
+
+    /// Get the ingress class annotated with `ingressclass.kubernetes.io/is-default-class`
+    /// Return `None` if there is no default ingress class
+    ///
+    async fn get_default_ingressclass(&self) -> client::Result<Option<networkingv1::IngressClass>> {
+        let default = self.list_ingressclasses().await?.into_iter().find(|class| {
+            class
+                .annotations()
+                .get("ingressclass.kubernetes.io/is-default-class")
+                .is_some_and(|value| value == "true")
+        });
+        Ok(default)
+    }
+
+    /// Get named mutating webhook configuration
+    /// Return `None` if not found
+    ///
+    async fn get_mutatingwebhookconfiguration_opt(
+        &self,
+        name: &str,
+    ) -> client::Result<Option<admissionregistrationv1::MutatingWebhookConfiguration>> {
+        self.get_cluster_k_opt(name).await
+    }
+
+    /// Get named mutating webhook configuration
+    ///
+    async fn get_mutatingwebhookconfiguration(
+        &self,
+        name: &str,
+    ) -> client::Result<admissionregistrationv1::MutatingWebhookConfiguration> {
+        self.get_cluster_k(name).await
+    }
+
+    /// List all `MutatingWebhookConfiguration`s
+    ///
+    async fn list_mutatingwebhookconfigurations(
+        &self,
+    ) -> client::Result<Vec<admissionregistrationv1::MutatingWebhookConfiguration>> {
+        self.list_cluster_k().await
+    }
+
+    /// Get named validating webhook configuration
+    /// Return `None` if not found
+    ///
+    async fn get_validatingwebhookconfiguration_opt(
+        &self,
+        name: &str,
+    ) -> client::Result<Option<admissionregistrationv1::ValidatingWebhookConfiguration>> {
+        self.get_cluster_k_opt(name).await
+    }
+
+    /// Get named validating webhook configuration
+    ///
+    async fn get_validatingwebhookconfiguration(
+        &self,
+        name: &str,
+    ) -> client::Result<admissionregistrationv1::ValidatingWebhookConfiguration> {
+        self.get_cluster_k(name).await
+    }
+
+    /// List all `ValidatingWebhookConfiguration`s
+    ///
+    async fn list_validatingwebhookconfigurations(
+        &self,
+    ) -> client::Result<Vec<admissionregistrationv1::ValidatingWebhookConfiguration>> {
+        self.list_cluster_k().await
+    }
+
+    /// Get names of the mutating and validating webhooks whose rules match
+    /// objects of a given `gvk`
+    ///
+    /// The kind is resolved to its resource via API discovery, and rules are matched
+    /// on `apiGroups`, `apiVersions` and `resources` (honoring `*` and `*/*` wildcards)
+    /// regardless of operations. Only exact API versions are considered, i.e.
+    /// `matchPolicy: Equivalent` conversions are not taken into account.
+    ///
+    async fn webhooks_targeting(&self, gvk: &api::GroupVersionKind) -> client::Result<Vec<String>> {
+        let (resource, _) = discovery::pinned_kind(&client_of(self), gvk).await?;
+        let (mutating, validating) = futures::try_join!(
+            self.list_mutatingwebhookconfigurations(),
+            self.list_validatingwebhookconfigurations(),
+        )?;
+        let mutating = mutating
+            .into_iter()
+            .flat_map(|config| config.webhooks.unwrap_or_default())
+            .filter(|webhook| rules_match(webhook.rules.as_deref(), gvk, &resource.plural))
+            .map(|webhook| webhook.name);
+        let validating = validating
+            .into_iter()
+            .flat_map(|config| config.webhooks.unwrap_or_default())
+            .filter(|webhook| rules_match(webhook.rules.as_deref(), gvk, &resource.plural))
+            .map(|webhook| webhook.name);
+        Ok(mutating.chain(validating).collect())
+    }
+
+    /// List API groups served under `/apis` along with their versions
+    /// Nothing is cached, so callers negotiating versions repeatedly should keep the result
+    ///
+    async fn api_groups(&self) -> client::Result<Vec<metav1::APIGroup>> {
+        client_of(self)
+            .list_api_groups()
+            .await
+            .map(|list| list.groups)
+    }
+
+    /// Get the version of API `group` preferred by the API server (e.g. `v1` for `batch`),
+    /// with `""` standing for the core group
+    /// Return `None` if the group is not served
+    ///
+    async fn preferred_version(&self, group: &str) -> client::Result<Option<String>> {
+        if group.is_empty() {
+            let versions = client_of(self).list_core_api_versions().await?.versions;
+            return Ok(versions.into_iter().next());
+        }
+        let version = self
+            .api_groups()
+            .await?
+            .into_iter()
+            .find(|api_group| api_group.name == group)
+            .and_then(|api_group| {
+                api_group
+                    .preferred_version
+                    .or_else(|| api_group.versions.into_iter().next())
+            })
+            .map(|version| version.version);
+        Ok(version)
+    }
+
+    /// Check whether objects of `kind` are served by the API server in `group`/`version`
+    /// (with `""` standing for the core group), e.g. to feature-detect newer APIs
+    /// Return `false` if the group version is not served at all
+    ///
+    async fn supports_resource(
+        &self,
+        group: &str,
+        version: &str,
+        kind: &str,
+    ) -> client::Result<bool> {
+        let client = &client_of(self);
+        let resources = if group.is_empty() {
+            client.list_core_api_resources(version).await
+        } else {
+            client
+                .list_api_group_resources(&format!("{group}/{version}"))
+                .await
+        };
+        match resources {
+            Ok(list) => Ok(list
+                .resources
+                .iter()
+                .any(|resource| resource.kind == kind && !resource.name.contains('/'))),
+            Err(client::Error::Api(status)) if status.is_not_found() => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Get named lease from a given (or default) namespace
+    /// Return `None` if not found
+    ///
+    async fn get_lease_opt(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Option<coordinationv1::Lease>> {
+        self.get_k_opt(name, namespace).await
+    }
+
+    /// Get named lease from a given (or default) namespace
+    ///
+    async fn get_lease(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<coordinationv1::Lease> {
+        self.get_k(name, namespace).await
+    }
+
+    /// Get identity of the current holder of named lease in a given (or default) namespace
+    /// Return `None` if the lease is not held
+    ///
+    async fn lease_holder(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Option<String>> {
+        let lease = self.get_lease(name, namespace).await?;
+        let holder = lease
+            .spec
+            .and_then(|spec| spec.holder_identity)
+            .filter(|holder| !holder.is_empty());
+        Ok(holder)
+    }
+
+    /// Get named CSI driver
+    /// Return `None` if not found
+    ///
+    async fn get_csidriver_opt(&self, name: &str) -> client::Result<Option<storagev1::CSIDriver>> {
+        self.get_cluster_k_opt(name).await
+    }
+
+    /// Get named CSI driver
+    ///
+    async fn get_csidriver(&self, name: &str) -> client::Result<storagev1::CSIDriver> {
+        self.get_cluster_k(name).await
+    }
+
+    /// Get named object of kind `K` from a given (or default) namespace
+    /// Return `None` if not found
+    ///
+    async fn get_k_opt<K>(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Option<K>>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.namespaced_k::<K>(namespace);
+        api.get_opt(name).await
+    }
+
+    /// Get named object of kind `K` from a given (or default) namespace
+    ///
+    async fn get_k<K>(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<K>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.namespaced_k::<K>(namespace);
+        api.get(name).await
+    }
+
+    /// Get named cluster-scoped object of kind `K`
+    /// Return `None` if not found
+    ///
+    async fn get_cluster_k_opt<K>(&self, name: &str) -> client::Result<Option<K>>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::ClusterResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.cluster_k::<K>();
+        api.get_opt(name).await
+    }
+
+    /// Get named cluster-scoped object of kind `K`
+    ///
+    async fn get_cluster_k<K>(&self, name: &str) -> client::Result<K>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::ClusterResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.cluster_k::<K>();
+        api.get(name).await
+    }
+
+    /// Check whether named object of kind `K` exists in a given (or default) namespace
+    ///
+    async fn exists<K>(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<bool>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.namespaced_k::<K>(namespace);
+        api.exists(name).await
+    }
+
+    /// Check whether named cluster-scoped object of kind `K` exists
+    ///
+    async fn cluster_exists<K>(&self, name: &str) -> client::Result<bool>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::ClusterResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.cluster_k::<K>();
+        api.exists(name).await
+    }
+
+    /// Get owner object from `ownerReference` assuming it is of kind `K`
+    ///
+    async fn get_owner_k<O, K>(&self, o: &O) -> client::Result<Option<K>>
+    where
+        O: client::ResourceExt + Sync,
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let dynamic_default = K::DynamicType::default();
+        let kind = K::kind(&dynamic_default);
+        let namespace = o.namespace();
+        if let Some(name) = o
+            .owner_references()
+            .iter()
+            .find(|owner| owner.kind == kind)
+            .map(|owner| &owner.name)
+        {
+            self.get_k_opt(name, namespace.as_deref()).await
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Resolve every owner reference of `object` (not only controllers), recursively up
+    /// to the roots, using API discovery to fetch owners of any kind
+    ///
+    /// Ancestors shared by several dependents are fetched once and appear as the same
+    /// [`OwnerNode`]. Owners that no longer exist are left out, references forming a
+    /// cycle are ignored and resolution stops [`OwnerNode::MAX_DEPTH`] levels above `object`.
+    ///
+    async fn owner_hierarchy(
+        &self,
+        object: &(impl k8s::openapi::serde::Serialize + Sync),
+    ) -> client::Result<OwnerNode> {
+        let root = json::to_value(object)
+            .and_then(json::from_value::<api::DynamicObject>)
+            .map_err(client::Error::SerdeError)?;
+        let namespace = root.metadata.namespace.clone();
+        let root_uid = root.metadata.uid.clone().unwrap_or_default();
+        let mut discovered = HashMap::new();
+        let mut owners = HashMap::<String, api::DynamicObject>::new();
+        let client = client_of(self);
+        let mut pending = VecDeque::from([(root.owner_references().to_vec(), 1)]);
+
+        while let Some((references, depth)) = pending.pop_front() {
+            if depth > OwnerNode::MAX_DEPTH {
+                continue;
+            }
+            for owner in references {
+                if owner.uid == root_uid || owners.contains_key(&owner.uid) {
+                    continue;
+                }
+                let found =
+                    get_owner_dynamic(&client, &owner, namespace.as_deref(), &mut discovered)
+                        .await?;
+                if let Some(found) = found {
+                    pending.push_back((found.owner_references().to_vec(), depth + 1));
+                    owners.insert(owner.uid, found);
+                }
+            }
+        }
+
+        Ok(OwnerNode::build(root, &owners))
+    }
+
+    /// Get the top level workload controlling the `pod`, following controller references
+    /// through intermediate objects (i.e. `Deployment` rather than its `ReplicaSet`,
+    /// `CronJob` rather than its `Job`)
+    /// Return `None` if the pod has no controller, or it is not a known workload kind
+    ///
+    async fn get_workload_for_pod(&self, pod: &corev1::Pod) -> client::Result<Option<Workload>> {
+        let namespace = pod.namespace();
+        let namespace = namespace.as_deref();
+        let Some(owner) = controller_of(pod) else {
+            return Ok(None);
+        };
+
+        let workload = match owner.kind.as_str() {
+            "ReplicaSet" => {
+                let Some(replicaset) = self
+                    .get_k_opt::<appsv1::ReplicaSet>(&owner.name, namespace)
+                    .await?
+                else {
+                    return Ok(None);
+                };
+                if let Some(owner) = controller_of(&replicaset).filter(|o| o.kind == "Deployment")
+                    && let Some(deployment) = self.get_k_opt(&owner.name, namespace).await?
+                {
+                    Some(Workload::Deployment(deployment))
+                } else {
+                    Some(Workload::ReplicaSet(replicaset))
+                }
+            }
+            "Job" => {
+                let Some(job) = self
+                    .get_k_opt::<batchv1::Job>(&owner.name, namespace)
+                    .await?
+                else {
+                    return Ok(None);
+                };
+                if let Some(owner) = controller_of(&job).filter(|o| o.kind == "CronJob")
+                    && let Some(cronjob) = self.get_k_opt(&owner.name, namespace).await?
+                {
+                    Some(Workload::CronJob(cronjob))
+                } else {
+                    Some(Workload::Job(job))
+                }
+            }
+            "StatefulSet" => self
+                .get_k_opt(&owner.name, namespace)
+                .await?
+                .map(Workload::StatefulSet),
+            "DaemonSet" => self
+                .get_k_opt(&owner.name, namespace)
+                .await?
+                .map(Workload::DaemonSet),
+            "ReplicationController" => self
+                .get_k_opt(&owner.name, namespace)
+                .await?
+                .map(Workload::ReplicationController),
+            _ => None,
+        };
+
+        Ok(workload)
+    }
+
+    /// Get labels of the top level workload controlling the `pod`
+    /// (see [`Self::get_workload_for_pod`]), useful to attribute the pod to its app
+    /// Return the pod's own labels if there is no such workload
+    ///
+    async fn workload_labels(&self, pod: &corev1::Pod) -> client::Result<BTreeMap<String, String>> {
+        let labels = match self.get_workload_for_pod(pod).await? {
+            Some(workload) => workload.metadata().labels.clone().unwrap_or_default(),
+            None => pod.labels().clone(),
+        };
+        Ok(labels)
+    }
+
+    /// Get name of the top level workload controlling the `pod`
+    /// (see [`Self::get_workload_for_pod`]), e.g. to label its logs or traces
+    /// Return the pod's own name if there is no such workload
+    ///
+    async fn workload_name_for_pod(&self, pod: &corev1::Pod) -> client::Result<Option<String>> {
+        let name = match self.get_workload_for_pod(pod).await? {
+            Some(workload) => workload.metadata().name.clone(),
+            None => pod.metadata.name.clone(),
+        };
+        Ok(name)
+    }
+
+    /// List all `Pod`s  in a given (or default) namespace
+    ///
+    async fn list_pods(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::Pod>> {
+        self.list_k(namespace).await
+    }
+
+    /// List pods in a given `phase` (`Pending`, `Running`, `Succeeded`, `Failed` or `Unknown`)
+    /// in a given (or default) namespace, filtered server-side with `status.phase` field selector
+    /// Return [`KubeExtError::InvalidArgument`] for unknown phase
+    ///
+    async fn list_pods_by_phase(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        phase: &str,
+    ) -> Result<Vec<corev1::Pod>, KubeExtError> {
+        if !POD_PHASES.contains(&phase) {
+            let message = format!(
+                "Unknown pod phase {phase}, expected one of {}",
+                POD_PHASES.join(", ")
+            );
+            return Err(KubeExtError::InvalidArgument(message));
+        }
+        let fields = FieldSelector::new().equal("status.phase", phase).build()?;
+        let lp = self.list_params().fields(&fields);
+        let pods = self.pods(namespace).list(&lp).await?.items;
+        Ok(pods)
+    }
+
+    /// List `Running` pods in a given (or default) namespace
+    ///
+    async fn list_running_pods(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> Result<Vec<corev1::Pod>, KubeExtError> {
+        self.list_pods_by_phase(namespace, "Running").await
+    }
+
+    /// List `Pending` pods in a given (or default) namespace
+    ///
+    async fn list_pending_pods(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> Result<Vec<corev1::Pod>, KubeExtError> {
+        self.list_pods_by_phase(namespace, "Pending").await
+    }
+
+    /// List `Failed` pods in a given (or default) namespace
+    ///
+    async fn list_failed_pods(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> Result<Vec<corev1::Pod>, KubeExtError> {
+        self.list_pods_by_phase(namespace, "Failed").await
+    }
+
+    /// Delete pods evicted by the kubelet (`Failed` with `Evicted` reason)
+    /// in a given (or default) namespace
+    /// Return the names of the deleted pods
+    ///
+    async fn delete_evicted_pods(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<String>> {
+        let namespace = namespace.into();
+        let pods = self.pods(namespace);
+        let dp = self.delete_params();
+        let mut deleted = Vec::new();
+        for pod in self.list_failed_pods(namespace).await? {
+            let evicted = pod
+                .status
+                .as_ref()
+                .is_some_and(|status| status.reason.as_deref() == Some("Evicted"));
+            if evicted {
+                let name = pod.name_any();
+                pods.delete(&name, &dp).await.or_else(not_found_ok)?;
+                deleted.push(name);
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// Watch events in a given (or default) namespace, like `kubectl get events -w` does,
+    /// optionally restricted with `field_selector` (e.g. `involvedObject.name=my-pod`)
+    ///
+    /// Only events happening after the call are reported. When the watch expires the stream
+    /// yields `WatchEvent::Error` with code `410` (`Gone`) and ends, signalling that
+    /// the caller should relist and start a new watch.
+    ///
+    async fn watch_events(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        field_selector: Option<&str>,
+    ) -> client::Result<BoxStream<'static, client::Result<api::WatchEvent<corev1::Event>>>> {
+        let events = self.events(namespace);
+        let mut lp = self.list_params().limit(1);
+        let mut wp = self.watch_params();
+        if let Some(selector) = field_selector {
+            lp = lp.fields(selector);
+            wp = wp.fields(selector);
+        }
+        let version = events
+            .list_metadata(&lp)
+            .await?
+            .metadata
+            .resource_version
+            .unwrap_or_default();
+        let stream = events
+            .watch(&wp, &version)
+            .await?
+            .scan(false, |expired, event| {
+                if *expired {
+                    return future::ready(None);
+                }
+                if let Ok(api::WatchEvent::Error(status)) = &event {
+                    *expired = status.code == 410;
+                }
+                future::ready(Some(event))
+            });
+        Ok(stream.boxed())
+    }
+
+    /// List events about the `object` (matched by `involvedObject.uid`) in its namespace
+    ///
+    async fn list_events_for_object<K>(
+        &self,
+        object: &K,
+    ) -> Result<Vec<corev1::Event>, KubeExtError>
+    where
+        K: client::Resource + Sync,
+    {
+        let Some(uid) = object.meta().uid.as_deref() else {
+            return Err(KubeExtError::MissingMetadata("uid"));
+        };
+        let fields = FieldSelector::new()
+            .equal("involvedObject.uid", uid)
+            .build()?;
+        let lp = self.list_params().fields(&fields);
+        let namespace = object.meta().namespace.as_deref();
+        let events = self.events(namespace).list(&lp).await?.items;
+        Ok(events)
+    }
+
+    /// List events about scaling decisions (e.g. `SuccessfulRescale` or `FailedGetResourceMetric`)
+    /// of named horizontal pod autoscaler in a given (or default) namespace, newest first
+    ///
+    async fn hpa_scaling_events(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::Event>> {
+        let hpa = self.horizontalpodautoscalers(namespace).get(name).await?;
+        let mut events = self.list_events_for_object(&hpa).await?;
+        events.retain(|event| {
+            event
+                .reason
+                .as_deref()
+                .is_some_and(|reason| HPA_SCALING_REASONS.contains(&reason))
+        });
+        events.sort_by_key(|event| std::cmp::Reverse(event_timestamp(event)));
+        Ok(events)
+    }
+
+    /// Count events in a given (or default) namespace by their `reason` (e.g. `BackOff`),
+    /// optionally only events of a given `event_type` (`Normal` or `Warning`)
+    /// and only events that happened within the last `window`
+    ///
+    /// The event time is its `lastTimestamp`, falling back to `eventTime` and
+    /// creation timestamp. Events without reason are not counted.
+    ///
+    async fn event_summary(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        event_type: Option<&str>,
+        window: Option<Duration>,
+    ) -> Result<BTreeMap<String, usize>, KubeExtError> {
+        let mut lp = self.list_params();
+        if let Some(event_type) = event_type {
+            let fields = FieldSelector::new().equal("type", event_type).build()?;
+            lp = lp.fields(&fields);
+        }
+        let now = k8s::openapi::jiff::Timestamp::now();
+        let since = window.map(|window| {
+            now.checked_sub(window)
+                .unwrap_or(k8s::openapi::jiff::Timestamp::MIN)
+        });
+        let events = self.events(namespace).list(&lp).await?.items;
+        let summary = events
+            .iter()
+            .filter(|event| {
+                since.is_none_or(|since| event_timestamp(event).is_some_and(|time| time >= since))
+            })
+            .filter_map(|event| event.reason.clone())
+            .fold(BTreeMap::new(), |mut summary, reason| {
+                *summary.entry(reason).or_default() += 1;
+                summary
+            });
+        Ok(summary)
+    }
+
+    /// Watch objects of kind `K` in a given (or default) namespace and call `handler`
+    /// for every `Added`, `Modified` and `Deleted` event, until `handler` fails
+    ///
+    /// This is a simple single-kind watch loop, not a replacement for `kube-runtime`
+    /// controllers. The objects are listed first and reported as `Added`, then watched
+    /// from the listed resource version. When the watch expires (`410 Gone`) the objects
+    /// are relisted, while other watch failures and transient list failures are retried
+    /// with backoff (see [`RetryPolicy`]), relisting as well. The backoff keeps growing
+    /// until a watch event is received. Listing that is forbidden (`401`, `403`) or
+    /// not found (`404`) is not going to recover and fails the reconciliation.
+    ///
+    /// Delivery is at-least-once: after every relist all the existing objects are
+    /// reported as `Added` again, so `handler` should be idempotent. Objects deleted
+    /// while the watch was interrupted are reported as `Deleted` (with their last
+    /// known state) once the relist shows they are gone.
+    ///
+    async fn reconcile<K, F, Fut>(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        handler: F,
+    ) -> client::Result<()>
+    where
+        K: Clone
+            + fmt::Debug
+            + Send
+            + 'static
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+        F: Fn(api::WatchEvent<K>) -> Fut + Send + Sync,
+        Fut: Future<Output = client::Result<()>> + Send,
+    {
+        let api = self.namespaced_k::<K>(namespace);
+        let lp = self.list_params();
+        let wp = self.watch_params();
+        let policy = RetryPolicy::default();
+        let mut failures = 0;
+        let mut known = HashMap::<String, K>::new();
+
+        'relist: loop {
+            let list = match api.list(&lp).await {
+                Ok(list) => list,
+                Err(client::Error::Api(status)) if matches!(status.code, 401 | 403 | 404) => {
+                    tracing::warn!(message = status.message, "list failed, giving up");
+                    return Err(client::Error::Api(status));
+                }
+                Err(err) => {
+                    failures += 1;
+                    tracing::warn!(%err, failures, "list failed, retrying");
+                    tokio::time::sleep(policy.backoff(failures)).await;
+                    continue;
+                }
+            };
+            let mut version = list.metadata.resource_version.unwrap_or_default();
+            let mut gone = std::mem::take(&mut known);
+            for object in list.items {
+                let key = object_key(&object);
+                gone.remove(&key);
+                known.insert(key, object.clone());
+                handler(api::WatchEvent::Added(object)).await?;
+            }
+            for object in gone.into_values() {
+                handler(api::WatchEvent::Deleted(object)).await?;
+            }
+
+            loop {
+                let mut events = match api.watch(&wp, &version).await {
+                    Ok(events) => events.boxed(),
+                    Err(err) => {
+                        failures += 1;
+                        tracing::warn!(%err, failures, "watch failed, relisting");
+                        tokio::time::sleep(policy.backoff(failures)).await;
+                        continue 'relist;
+                    }
+                };
+                while let Some(event) = events.next().await {
+                    match event {
+                        Ok(api::WatchEvent::Bookmark(bookmark)) => {
+                            version = bookmark.metadata.resource_version;
+                        }
+                        Ok(api::WatchEvent::Error(status)) if status.code == 410 => {
+                            tracing::debug!(message = status.message, "watch expired, relisting");
+                            continue 'relist;
+                        }
+                        Ok(api::WatchEvent::Error(status)) => {
+                            failures += 1;
+                            tracing::warn!(
+                                message = status.message,
+                                failures,
+                                "watch error, relisting"
+                            );
+                            tokio::time::sleep(policy.backoff(failures)).await;
+                            continue 'relist;
+                        }
+                        Ok(event) => {
+                            failures = 0;
+                            let object = match &event {
+                                api::WatchEvent::Added(object)
+                                | api::WatchEvent::Modified(object) => {
+                                    known.insert(object_key(object), object.clone());
+                                    Some(object)
+                                }
+                                api::WatchEvent::Deleted(object) => {
+                                    known.remove(&object_key(object));
+                                    Some(object)
+                                }
+                                _ => None,
+                            };
+                            if let Some(object_version) =
+                                object.and_then(|object| object.resource_version())
+                            {
+                                version = object_version;
+                            }
+                            handler(event).await?;
+                        }
+                        Err(err) => {
+                            failures += 1;
+                            tracing::warn!(%err, failures, "watch failed, relisting");
+                            tokio::time::sleep(policy.backoff(failures)).await;
+                            continue 'relist;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// List pods in a given (or default) namespace with a container (or init container)
+    /// waiting in `CrashLoopBackOff` (see [`KubeClientExt::pod_is_crashlooping`])
+    ///
+    async fn list_crashlooping_pods(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::Pod>> {
+        let mut pods = self.list_pods(namespace).await?;
+        pods.retain(|pod| self.pod_is_crashlooping(pod));
+        Ok(pods)
+    }
+
+    /// List all `Deployment`s in a given (or default) namespace
+    ///
+    async fn list_deployments(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<appsv1::Deployment>> {
+        self.list_k(namespace).await
+    }
+
+    /// List all `ReplicaSets` in a given (or default) namespace
+    ///
+    async fn list_replicasets(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<appsv1::ReplicaSet>> {
+        self.list_k(namespace).await
+    }
+
+    /// List all `ReplicationController`s in a given (or default) namespace
+    ///
+    async fn list_replicationcontrollers(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::ReplicationController>> {
+        self.list_k(namespace).await
+    }
+
+    /// List all `Ingress`es in a given (or default) namespace
+    ///
+    async fn list_ingresses(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<networkingv1::Ingress>> {
+        self.list_k(namespace).await
+    }
+
+    /// List all `Job`s in a given (or default) namespace
+    ///
+    async fn list_jobs(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<batchv1::Job>> {
+        self.list_k(namespace).await
+    }
+
+    /// Delete `Job`s in a given (or default) namespace that completed successfully
+    /// and were created more than `older_than` ago, along with their pods
+    /// (i.e. housekeeping for jobs without `ttlSecondsAfterFinished`)
+    /// Return the names of the deleted jobs
+    ///
+    async fn delete_completed_jobs(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        older_than: Duration,
+    ) -> client::Result<Vec<String>> {
+        let namespace = namespace.into();
+        let jobs = self.jobs(namespace);
+        let dp = self.foreground_delete();
+        let mut deleted = Vec::new();
+        for job in self.list_jobs(namespace).await? {
+            let completed = JobStats::new(&job).state == JobState::Succeeded;
+            if completed && is_older_than(&job, older_than) {
+                let name = job.name_any();
+                jobs.delete(&name, &dp).await.or_else(not_found_ok)?;
+                deleted.push(name);
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// List all `CronJob`s in a given (or default) namespace
+    ///
+    async fn list_cronjobs(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<batchv1::CronJob>> {
+        self.list_k(namespace).await
+    }
+
+    /// List all `Secret`s in a given (or default) namespace
+    ///
+    async fn list_secrets(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::Secret>> {
+        self.list_k(namespace).await
+    }
+
+    /// List `Secret`s of a given `type` (e.g. `kubernetes.io/tls`) in a given (or default) namespace
+    /// Filtering is done server-side with `type=` field selector, falling back to
+    /// client-side filtering if the API server rejects the selector
+    ///
+    async fn list_secrets_of_type(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        secret_type: &str,
+    ) -> Result<Vec<corev1::Secret>, KubeExtError> {
+        let namespace = namespace.into();
+        let fields = FieldSelector::new().equal("type", secret_type).build()?;
+        let lp = self.list_params().fields(&fields);
+        match self.secrets(namespace).list(&lp).await {
+            Ok(list) => Ok(list.items),
+            Err(client::Error::Api(status)) if status.code == 400 => {
+                tracing::debug!(
+                    secret_type,
+                    message = status.message,
+                    "type field selector rejected, filtering client-side"
+                );
+                let secrets = self.list_secrets(namespace).await?;
+                let secrets = secrets
+                    .into_iter()
+                    .filter(|secret| secret.type_.as_deref() == Some(secret_type))
+                    .collect();
+                Ok(secrets)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// List TLS (`kubernetes.io/tls`) `Secret`s in a given (or default) namespace
+    ///
+    async fn list_tls_secrets(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> Result<Vec<corev1::Secret>, KubeExtError> {
+        self.list_secrets_of_type(namespace, corev1::Secret::SECRET_TYPE_TLS)
+            .await
+    }
+
+    /// List registry credential `Secret`s in a given (or default) namespace
+    /// Both `kubernetes.io/dockerconfigjson` and legacy `kubernetes.io/dockercfg`
+    /// secrets are returned
+    ///
+    async fn list_dockercfg_secrets(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> Result<Vec<corev1::Secret>, KubeExtError> {
+        let namespace = namespace.into();
+        let (mut config_json, dockercfg) = futures::try_join!(
+            self.list_secrets_of_type(namespace, corev1::Secret::SECRET_TYPE_DOCKER_CONFIG_JSON),
+            self.list_secrets_of_type(namespace, corev1::Secret::SECRET_TYPE_DOCKERCFG),
+        )?;
+        config_json.extend(dockercfg);
+        Ok(config_json)
+    }
+
+    /// List all `Service`s in a given (or default) namespace
+    ///
+    async fn list_services(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::Service>> {
+        self.list_k(namespace).await
+    }
+
+    /// List all `StatefulSet`s in a given (or default) namespace
+    ///
+    async fn list_statefulsets(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<appsv1::StatefulSet>> {
+        self.list_k(namespace).await
+    }
+
+    /// List all `ConfigMap`s in a given (or default) namespace
+    ///
+    async fn list_configmaps(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::ConfigMap>> {
+        self.list_k(namespace).await
+    }
+
+    /// List all `ServiceAccount`s in a given (or default) namespace
+    ///
+    async fn list_serviceaccounts(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::ServiceAccount>> {
+        self.list_k(namespace).await
+    }
+
+    /// Get image pull secrets referenced by `imagePullSecrets` of named service account
+    /// in a given (or default) namespace, i.e. those added to the pods running under it
+    /// Secrets that no longer exist are skipped
+    ///
+    async fn serviceaccount_pull_secrets(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::Secret>> {
+        let namespace = namespace.into();
+        let serviceaccount = self.serviceaccounts(namespace).get(name).await?;
+        let mut secrets = vec![];
+        for reference in serviceaccount.image_pull_secrets.iter().flatten() {
+            if let Some(secret) = self.get_secret_opt(&reference.name, namespace).await? {
+                secrets.push(secret);
+            }
+        }
+        Ok(secrets)
+    }
+
+    /// Get the service account the `pod` runs as, named by its `serviceAccountName`
+    /// (or `default` if not set), falling back to the `default` service account
+    /// of the pod's namespace if the named one does not exist
+    /// Return `None` if neither exists
+    ///
+    async fn pod_service_account(
+        &self,
+        pod: &corev1::Pod,
+    ) -> client::Result<Option<corev1::ServiceAccount>> {
+        let namespace = pod.namespace();
+        let serviceaccounts = self.serviceaccounts(namespace.as_deref());
+        let name = pod
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.service_account_name.as_deref())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("default");
+        match serviceaccounts.get_opt(name).await? {
+            None if name != "default" => serviceaccounts.get_opt("default").await,
+            serviceaccount => Ok(serviceaccount),
+        }
+    }
+
+    /// Get the roles bound to named service account in a given (or default) namespace
+    /// by the role bindings of that namespace and by cluster role bindings
+    ///
+    /// Bindings match when one of their subjects is the service account itself, its
+    /// `system:serviceaccount:<namespace>:<name>` user, or one of the `system:serviceaccounts`
+    /// and `system:serviceaccounts:<namespace>` groups. Role bindings may reference both
+    /// `Role`s and `ClusterRole`s. Each role is reported once, in binding order.
+    ///
+    async fn roles_for_serviceaccount(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<rbacv1::RoleRef>> {
+        let namespace = self.resolve_namespace(namespace.into());
+        let (rolebindings, clusterrolebindings) = futures::try_join!(
+            self.list_k::<rbacv1::RoleBinding>(namespace.as_str()),
+            self.list_cluster_k::<rbacv1::ClusterRoleBinding>(),
+        )?;
+        let bound = |subjects: Option<&Vec<rbacv1::Subject>>| {
+            subjects
+                .into_iter()
+                .flatten()
+                .any(|subject| subject_is_serviceaccount(subject, name, &namespace))
+        };
+        let role_refs = rolebindings
+            .into_iter()
+            .filter(|binding| bound(binding.subjects.as_ref()))
+            .map(|binding| binding.role_ref);
+        let clusterrole_refs = clusterrolebindings
+            .into_iter()
+            .filter(|binding| bound(binding.subjects.as_ref()))
+            .map(|binding| binding.role_ref);
+
+        let mut roles = Vec::new();
+        for role_ref in role_refs.chain(clusterrole_refs) {
+            if !roles.contains(&role_ref) {
+                roles.push(role_ref);
+            }
+        }
+        Ok(roles)
+    }
+
+    /// Get the cluster roles aggregated into named cluster role, i.e. those matched by
+    /// any of the `clusterRoleSelectors` of its `aggregationRule`, sorted by name
+    /// Return an empty list if the cluster role does not aggregate other roles
+    ///
+    async fn aggregated_clusterroles(
+        &self,
+        name: &str,
+    ) -> client::Result<Vec<rbacv1::ClusterRole>> {
+        let clusterrole = self.clusterroles().get(name).await?;
+        let selectors = clusterrole
+            .aggregation_rule
+            .and_then(|rule| rule.cluster_role_selectors)
+            .unwrap_or_default();
+        if selectors.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut members = self.list_cluster_k::<rbacv1::ClusterRole>().await?;
+        members.retain(|member| {
+            member.metadata.name.as_deref() != Some(name)
+                && selectors
+                    .iter()
+                    .any(|selector| selector_matches(selector, member.labels()))
+        });
+        members.sort_by(|a, b| a.metadata.name.cmp(&b.metadata.name));
+        Ok(members)
+    }
+
+    /// Get the effective rules of named cluster role: its own rules followed by the rules
+    /// of the cluster roles aggregated into it (see [`Self::aggregated_clusterroles`])
+    /// Identical rules are reported once
+    ///
+    async fn effective_rules(
+        &self,
+        clusterrole_name: &str,
+    ) -> client::Result<Vec<rbacv1::PolicyRule>> {
+        let (clusterrole, members) = futures::try_join!(
+            self.get_cluster_k::<rbacv1::ClusterRole>(clusterrole_name),
+            self.aggregated_clusterroles(clusterrole_name),
+        )?;
+        let mut rules = Vec::new();
+        for rule in std::iter::once(clusterrole)
+            .chain(members)
+            .flat_map(|role| role.rules.unwrap_or_default())
+        {
+            if !rules.contains(&rule) {
+                rules.push(rule);
+            }
+        }
+        Ok(rules)
+    }
+
+    /// List all `Lease`s in a given (or default) namespace
+    ///
+    async fn list_leases(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<coordinationv1::Lease>> {
+        self.list_k(namespace).await
+    }
+
+    /// Get externally reachable addresses of named service in a given (or default) namespace
+    /// Depending on service type these are
+    /// - load balancer ingress `ip:port` (or `hostname:port`) for `LoadBalancer`
+    /// - `node:nodePort` for every node address for `NodePort`
+    /// - external name for `ExternalName`
+    ///
+    /// extended by `externalIP:port` for any explicitly set external IPs
+    /// Services with no external exposure yield an empty list
+    ///
+    async fn get_service_external_endpoints(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<String>> {
+        let service = self.services(namespace).get(name).await?;
+        let ports = service.ports().unwrap_or_default();
+        let with_ports = |address: &str| {
+            ports
+                .iter()
+                .map(|port| format!("{address}:{}", port.port))
+                .collect::<Vec<_>>()
+        };
+
+        let mut endpoints = vec![];
+        if service.is_external_name() {
+            endpoints.extend(service.external_name().map(ToString::to_string));
+        } else if service.is_load_balancer() {
+            service
+                .load_balancer_ingress()
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|ingress| ingress.ip.as_deref().or(ingress.hostname.as_deref()))
+                .for_each(|address| endpoints.extend(with_ports(address)));
+        } else if service.is_node_port() {
+            let nodes = self.list_cluster_k::<corev1::Node>().await?;
+            for address in nodes.iter().filter_map(node_address) {
+                endpoints.extend(
+                    ports
+                        .iter()
+                        .filter_map(|port| port.node_port)
+                        .map(|node_port| format!("{address}:{node_port}")),
+                );
+            }
+        }
+
+        service
+            .external_ips()
+            .unwrap_or_default()
+            .iter()
+            .for_each(|address| endpoints.extend(with_ports(address)));
+
+        Ok(endpoints)
+    }
+
+    /// Count ready and not ready endpoints of named service in a given (or default) namespace
+    /// reading its `EndpointSlice`s (see [`EndpointHealth`])
+    /// A service without endpoint slices reports no endpoints at all
+    ///
+    async fn service_endpoint_health(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> Result<EndpointHealth, KubeExtError> {
+        let labels = LabelSelector::new()
+            .equal("kubernetes.io/service-name", name)
+            .build()?;
+        let lp = self.list_params().labels(&labels);
+        let slices = self
+            .namespaced_k::<discoveryv1::EndpointSlice>(namespace)
+            .list(&lp)
+            .await?
+            .items;
+        Ok(EndpointHealth::from_slices(&slices))
+    }
+
+    /// Get all the ingresses in a given (or default) namespace routing to the named service,
+    /// either through one of the rule paths or the default backend
+    ///
+    async fn get_ingresses_for_service(
+        &self,
+        service_name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<networkingv1::Ingress>> {
+        let ingresses = self
+            .list_ingresses(namespace)
+            .await?
+            .into_iter()
+            .filter(|ingress| {
+                ingress_backends(ingress)
+                    .any(|backend| backend_service_name(backend) == Some(service_name))
+            })
+            .collect();
+        Ok(ingresses)
+    }
+
+    /// Resolve every service backend of the `ingress` into the service it points to
+    /// Each entry is labeled by `host/path` of its rule (`*` stands for any host),
+    /// or `default` for the default backend, and carries `None` if the service is missing
+    /// Resource (non-service) backends are skipped
+    ///
+    async fn resolve_ingress_backends(
+        &self,
+        ingress: &networkingv1::Ingress,
+    ) -> client::Result<Vec<(String, Option<corev1::Service>)>> {
+        let namespace = ingress.namespace();
+        let mut resolved = vec![];
+        for (label, name) in ingress_service_backends(ingress) {
+            let service = self.services(namespace.as_deref()).get_opt(name).await?;
+            resolved.push((label, service));
+        }
+        Ok(resolved)
+    }
+
+    /// Get a snapshot of the most common workload kinds in a given (or default) namespace,
+    /// similar to what `kubectl get all` shows
+    /// Kinds the caller is not allowed to list are left empty rather than failing the call
+    ///
+    async fn get_all(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<KubectlAll> {
+        let namespace = namespace.into();
+        let (
+            pods,
+            services,
+            deployments,
+            replicasets,
+            statefulsets,
+            daemonsets,
+            jobs,
+            cronjobs,
+            replicationcontrollers,
+        ) = futures::try_join!(
+            permitted(self.list_pods(namespace)),
+            permitted(self.list_services(namespace)),
+            permitted(self.list_deployments(namespace)),
+            permitted(self.list_replicasets(namespace)),
+            permitted(self.list_statefulsets(namespace)),
+            permitted(self.list_k(namespace)),
+            permitted(self.list_jobs(namespace)),
+            permitted(self.list_cronjobs(namespace)),
+            permitted(self.list_replicationcontrollers(namespace)),
+        )?;
+
+        Ok(KubectlAll {
+            pods,
+            services,
+            deployments,
+            replicasets,
+            statefulsets,
+            daemonsets,
+            jobs,
+            cronjobs,
+            replicationcontrollers,
+        })
+    }
+
+    /// List namespaced objects of kind `K` in a given (or default) namespace
+    ///
+    async fn list_k<K>(&self, namespace: impl Into<Option<&str>> + Send) -> client::Result<Vec<K>>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.namespaced_k::<K>(namespace);
+        let lp = self.list_params();
+        api.list(&lp).await.map(|list| list.items)
+    }
+
+    /// List namespaced objects of kind `K` in a given (or default) namespace
+    /// sorted with `cmp` (the sort is stable)
+    ///
+    async fn list_k_sorted<K, F>(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        cmp: F,
+    ) -> client::Result<Vec<K>>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+        F: FnMut(&K, &K) -> Ordering + Send,
+    {
+        let mut items = self.list_k(namespace).await?;
+        items.sort_by(cmp);
+        Ok(items)
+    }
+
+    /// List namespaced objects of kind `K` in a given (or default) namespace
+    /// annotated with `key` (and, if given, with the annotation set to `value`)
+    /// Annotations are not selectable server-side, so objects are filtered client-side
+    ///
+    async fn list_k_by_annotation<K>(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        key: &str,
+        value: Option<&str>,
+    ) -> client::Result<Vec<K>>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let mut items = self.list_k::<K>(namespace).await?;
+        items.retain(|item| {
+            item.annotations()
+                .get(key)
+                .is_some_and(|annotation| value.is_none_or(|value| annotation == value))
+        });
+        Ok(items)
+    }
+
+    /// List all `Namespace`s
+    ///
+    async fn list_namespaces(&self) -> client::Result<Vec<corev1::Namespace>> {
+        self.list_cluster_k().await
+    }
+
+    /// List all `Namespace`s except those being terminated
+    ///
+    async fn list_active_namespaces(&self) -> client::Result<Vec<corev1::Namespace>> {
+        let mut namespaces = self.list_namespaces().await?;
+        namespaces.retain(|namespace| {
+            namespace
+                .status
+                .as_ref()
+                .and_then(|status| status.phase.as_deref())
+                != Some("Terminating")
+        });
+        Ok(namespaces)
+    }
+
+    /// Check whether named namespace exists
+    ///
+    async fn namespace_exists(&self, name: &str) -> client::Result<bool> {
+        self.cluster_exists::<corev1::Namespace>(name).await
+    }
+
+    /// Run `f` for the name of every namespace, with at most `concurrency` calls in flight
+    /// Results are collected in completion order (not in namespace order) and
+    /// the first failure is returned as error
+    ///
+    async fn for_each_namespace<F, Fut, T>(
+        &self,
+        concurrency: usize,
+        f: F,
+    ) -> client::Result<Vec<T>>
+    where
+        F: Fn(String) -> Fut + Send + Sync,
+        Fut: Future<Output = client::Result<T>> + Send,
+        T: Send,
+    {
+        let names = self
+            .list_namespaces()
+            .await?
+            .into_iter()
+            .map(|namespace| namespace.name_any());
+        futures::stream::iter(names)
+            .map(f)
+            .buffer_unordered(concurrency.max(1))
+            .try_collect()
+            .await
+    }
+
+    /// List all `Node`s
+    ///
+    async fn list_nodes(&self) -> client::Result<Vec<corev1::Node>> {
+        self.list_cluster_k().await
+    }
+
+    /// Check whether the named node is ready
+    ///
+    async fn node_is_ready_by_name(&self, name: &str) -> client::Result<bool> {
+        let node = self.nodes().get(name).await?;
+        Ok(self.node_is_ready(&node))
+    }
+
+    /// Get pods across all namespaces scheduled to nodes that are not ready
+    /// Nodes and pods are listed once each and joined by `spec.nodeName`
+    ///
+    async fn pods_on_not_ready_nodes(&self) -> client::Result<Vec<corev1::Pod>> {
+        let not_ready = self
+            .list_nodes()
+            .await?
+            .into_iter()
+            .filter(|node| !self.node_is_ready(node))
+            .map(|node| node.name_any())
+            .collect::<BTreeSet<_>>();
+        if not_ready.is_empty() {
+            return Ok(vec![]);
+        }
+        let lp = self.list_params();
+        let pods = self
+            .api::<corev1::Pod>()
+            .list(&lp)
+            .await?
+            .items
+            .into_iter()
+            .filter(|pod| {
+                pod.spec
+                    .as_ref()
+                    .and_then(|spec| spec.node_name.as_ref())
+                    .is_some_and(|node| not_ready.contains(node))
+            })
+            .collect();
+        Ok(pods)
+    }
+
+    /// Get the node hosting the `pod`
+    /// Return `None` if the pod is not scheduled yet or its node is gone
+    ///
+    async fn get_node_for_pod(&self, pod: &corev1::Pod) -> client::Result<Option<corev1::Node>> {
+        let Some(node_name) = self.pod_node_name(pod) else {
+            return Ok(None);
+        };
+        self.get_cluster_k_opt(node_name).await
+    }
+
+    /// Get all the pods scheduled to the named node across all namespaces
+    ///
+    async fn get_pods_on_node(&self, node_name: &str) -> Result<Vec<corev1::Pod>, KubeExtError> {
+        let fields = FieldSelector::new()
+            .equal("spec.nodeName", node_name)
+            .build()?;
+        let lp = self.list_params().fields(&fields);
+        let pods = self.api::<corev1::Pod>().list(&lp).await?.items;
+        Ok(pods)
+    }
+
+    /// Compare the sum of CPU and memory requests of the pods scheduled to the named node
+    /// with the node's allocatable capacity
+    /// Note that this is request based utilization, not actual usage
+    ///
+    async fn node_utilization(&self, node_name: &str) -> Result<NodeUtilization, KubeExtError> {
+        let node = self.nodes().get(node_name).await?;
+        let allocatable = node
+            .status
+            .as_ref()
+            .and_then(|status| status.allocatable.as_ref());
+        let allocated = |name: &str| allocatable.and_then(|allocatable| allocatable.get(name));
+        let cpu_allocatable = allocated("cpu").map(parse_cpu).unwrap_or_default();
+        let memory_allocatable = allocated("memory").map(parse_memory).unwrap_or_default();
+
+        let requested = self
+            .get_pods_on_node(node_name)
+            .await?
+            .iter()
+            .filter(|pod| {
+                !matches!(
+                    pod.status
+                        .as_ref()
+                        .and_then(|status| status.phase.as_deref()),
+                    Some("Succeeded" | "Failed")
+                )
+            })
+            .map(ResourceTotals::requests)
+            .fold(ResourceTotals::default(), |total, pod| total + pod);
+
+        Ok(NodeUtilization {
+            cpu_requested: requested.cpu,
+            cpu_allocatable,
+            memory_requested: requested.memory,
+            memory_allocatable,
+        })
+    }
+
+    /// Get pods across all namespaces whose actual CPU or memory usage, as reported by
+    /// `metrics.k8s.io` (i.e. `kubectl top pods`), exceeds their requests, along with that usage
+    /// Pods without requests count as exceeding them, pods without metrics are skipped
+    ///
+    async fn pods_exceeding_requests(&self) -> client::Result<Vec<(corev1::Pod, ResourceTotals)>> {
+        let gvk = api::GroupVersionKind::gvk("metrics.k8s.io", "v1beta1", "PodMetrics");
+        let resource = api::ApiResource::from_gvk_with_plural(&gvk, "pods");
+        let lp = self.list_params();
+        let usage = api::Api::<api::DynamicObject>::all_with(client_of(self), &resource)
+            .list(&lp)
+            .await?
+            .items
+            .iter()
+            .map(|metrics| {
+                let key = (metrics.namespace(), metrics.name_any());
+                (key, ResourceTotals::usage(metrics))
+            })
+            .collect::<HashMap<_, _>>();
+        let pods = self
+            .api::<corev1::Pod>()
+            .list(&lp)
+            .await?
+            .items
+            .into_iter()
+            .filter_map(|pod| {
+                let usage = *usage.get(&(pod.namespace(), pod.name_any()))?;
+                usage
+                    .exceeds(&ResourceTotals::requests(&pod))
+                    .then_some((pod, usage))
+            })
+            .collect();
+        Ok(pods)
+    }
+
+    /// Get the pods scheduled to the named node in a given (or default) namespace
+    ///
+    async fn get_pods_on_node_in(
+        &self,
+        node_name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> Result<Vec<corev1::Pod>, KubeExtError> {
+        let fields = FieldSelector::new()
+            .equal("spec.nodeName", node_name)
+            .build()?;
+        let lp = self.list_params().fields(&fields);
+        let pods = self.pods(namespace).list(&lp).await?.items;
+        Ok(pods)
+    }
+
+    /// Get the most recently created pod matching label `selector` in a given (or default) namespace
+    /// Pods created at the same time are ordered by name and the last one wins
+    /// Return `None` if no pod matches
+    ///
+    async fn get_latest_pod(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        selector: &str,
+    ) -> client::Result<Option<corev1::Pod>> {
+        let lp = self.list_params().labels(selector);
+        let pod = self
+            .pods(namespace)
+            .list(&lp)
+            .await?
+            .items
+            .into_iter()
+            .max_by(|a, b| {
+                a.creation_timestamp()
+                    .cmp(&b.creation_timestamp())
+                    .then_with(|| a.metadata.name.cmp(&b.metadata.name))
+            });
+        Ok(pod)
+    }
+
+    /// List all `Pod`s in a given (or default) namespace, oldest first
+    ///
+    async fn list_pods_by_age(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::Pod>> {
+        self.list_k_sorted(namespace, |a: &corev1::Pod, b: &corev1::Pod| {
+            a.creation_timestamp().cmp(&b.creation_timestamp())
+        })
+        .await
+    }
+
+    /// List all `Pod`s in a given (or default) namespace, sorted by name
+    ///
+    async fn list_pods_by_name(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::Pod>> {
+        self.list_k_sorted(namespace, |a: &corev1::Pod, b: &corev1::Pod| {
+            a.metadata.name.cmp(&b.metadata.name)
+        })
+        .await
+    }
+
+    /// Find `Pod`s in a given (or default) namespace whose name starts with `prefix`
+    /// (i.e. the pods of a `StatefulSet` or `Job` when only its name is known), sorted by name
+    ///
+    async fn find_pods_by_name_prefix(
+        &self,
+        prefix: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<corev1::Pod>> {
+        let mut pods = self.list_pods_by_name(namespace).await?;
+        pods.retain(|pod| {
+            pod.metadata
+                .name
+                .as_deref()
+                .is_some_and(|name| name.starts_with(prefix))
+        });
+        Ok(pods)
+    }
+
+    /// List `Pod`s in a given (or default) namespace annotated with `key`
+    /// (and, if given, with the annotation set to `value`)
+    ///
+    async fn list_pods_by_annotation(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        key: &str,
+        value: Option<&str>,
+    ) -> client::Result<Vec<corev1::Pod>> {
+        self.list_k_by_annotation(namespace, key, value).await
+    }
+
+    /// Get logs of a `container` (or of the only container if not given) of named pod
+    /// in a given (or default) namespace written at or after `since`
+    /// The timestamp is sent as RFC 3339 `sinceTime` with second precision
+    ///
+    async fn get_pod_logs_since(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+        container: Option<&str>,
+        since: k8s::openapi::jiff::Timestamp,
+    ) -> client::Result<String> {
+        let lp = api::LogParams {
+            container: container.map(ToString::to_string),
+            since_time: Some(since),
+            ..self.log_params()
+        };
+        self.pods(namespace).logs(name, &lp).await
+    }
+
+    /// Get logs of every (init, regular and ephemeral) container of named pod
+    /// in a given (or default) namespace, keyed by container name
+    ///
+    /// Logs are fetched concurrently, and failures (e.g. containers that have not
+    /// started yet) are recorded per container instead of failing the whole call.
+    ///
+    async fn get_all_container_logs(
+        &self,
+        pod_name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<BTreeMap<String, client::Result<String>>> {
+        let pods = self.pods(namespace);
+        let pod = pods.get(pod_name).await?;
+        let spec = pod.spec.as_ref();
+        let names = spec
+            .into_iter()
+            .flat_map(|spec| {
+                let init = spec
+                    .init_containers
+                    .iter()
+                    .flatten()
+                    .map(|container| &container.name);
+                let regular = spec.containers.iter().map(|container| &container.name);
+                let ephemeral = spec
+                    .ephemeral_containers
+                    .iter()
+                    .flatten()
+                    .map(|container| &container.name);
+                init.chain(regular).chain(ephemeral)
+            })
+            .collect::<Vec<_>>();
+        let logs = names
+            .iter()
+            .map(|&name| {
+                let lp = api::LogParams {
+                    container: Some(name.clone()),
+                    ..self.log_params()
+                };
+                let pods = &pods;
+                async move { (name.clone(), pods.logs(pod_name, &lp).await) }
+            })
+            .collect::<Vec<_>>();
+        let logs = futures::stream::iter(logs)
+            .buffer_unordered(CONCURRENCY)
+            .collect()
+            .await;
+        Ok(logs)
+    }
+
+    /// Follow logs of a `container` (or of the only container if not given) of all the pods
+    /// matching label `selector` in a given (or default) namespace, like `stern` does,
+    /// merged into a single stream of `(pod name, line)` pairs
+    ///
+    /// The set of pods is fixed when the call is made: pods created afterwards are not
+    /// picked up, and pods whose logs cannot be streamed yet (e.g. containers still
+    /// being created) are skipped. The stream ends when all the followed pods are gone.
+    ///
+    async fn stream_logs_for_selector(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        selector: &str,
+        container: Option<&str>,
+    ) -> client::Result<BoxStream<'static, client::Result<(String, String)>>> {
+        let pods = self.pods(namespace);
+        let lp = self.list_params().labels(selector);
+        let log_params = api::LogParams {
+            container: container.map(ToString::to_string),
+            follow: true,
+            ..self.log_params()
+        };
+        let mut streams = vec![];
+        for name in pods.list(&lp).await?.items.iter().map(|pod| pod.name_any()) {
+            let logs = match pods.log_stream(&name, &log_params).await {
+                Ok(logs) => logs,
+                Err(client::Error::Api(status)) if status.code == 400 => {
+                    tracing::debug!(
+                        pod = name,
+                        message = status.message,
+                        "cannot stream logs, skipping"
+                    );
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            let lines = logs.lines().map(move |line| {
+                line.map(|line| (name.clone(), line))
+                    .map_err(client::Error::ReadEvents)
+            });
+            streams.push(lines.boxed());
+        }
+        Ok(futures::stream::select_all(streams).boxed())
+    }
+
+    /// List one page (at most `limit` items) of namespaced objects of kind `K`
+    /// in a given (or default) namespace, starting from `continue_token`
+    /// Return the items along with the continue token for the next page,
+    /// which is `None` when there are no more pages
+    ///
+    async fn list_k_page<K>(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+        continue_token: Option<&str>,
+        limit: u32,
+    ) -> client::Result<(Vec<K>, Option<String>)>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let mut lp = self.list_params().limit(limit);
+        if let Some(token) = continue_token {
+            lp = lp.continue_token(token);
+        }
+        let api = self.namespaced_k::<K>(namespace);
+        let list = api.list(&lp).await?;
+        let next = list.metadata.continue_.filter(|token| !token.is_empty());
+        Ok((list.items, next))
+    }
+
+    /// Count pods, deployments, services, configmaps, secrets and persistent volume claims
+    /// in a given (or default) namespace, concurrently
+    /// Kinds the caller is not allowed to list are reported as `None`
+    ///
+    async fn namespace_summary(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<NamespaceSummary> {
+        let namespace = namespace.into();
+        let (pods, deployments, services, configmaps, secrets, persistentvolumeclaims) = futures::try_join!(
+            permitted_count::<corev1::Pod>(self.count_k::<corev1::Pod>(namespace)),
+            permitted_count::<appsv1::Deployment>(self.count_k::<appsv1::Deployment>(namespace)),
+            permitted_count::<corev1::Service>(self.count_k::<corev1::Service>(namespace)),
+            permitted_count::<corev1::ConfigMap>(self.count_k::<corev1::ConfigMap>(namespace)),
+            permitted_count::<corev1::Secret>(self.count_k::<corev1::Secret>(namespace)),
+            permitted_count::<corev1::PersistentVolumeClaim>(
+                self.count_k::<corev1::PersistentVolumeClaim>(namespace)
+            ),
+        )?;
+
+        Ok(NamespaceSummary {
+            pods,
+            deployments,
+            services,
+            configmaps,
+            secrets,
+            persistentvolumeclaims,
+        })
+    }
+
+    /// Count namespaced objects of kind `K` in a given (or default) namespace
+    /// without transferring all of them
+    ///
+    /// Lists a single object and relies on `metadata.remainingItemCount` reported by
+    /// the API server, which is an estimate and may be slightly off when objects are
+    /// being created or deleted concurrently. When the server does not report it
+    /// (e.g. when label or field selectors are involved) falls back to listing
+    /// the metadata of all objects.
+    ///
+    async fn count_k<K>(&self, namespace: impl Into<Option<&str>> + Send) -> client::Result<usize>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.namespaced_k::<K>(namespace);
+        let lp = self.list_params();
+        count_objects(&api, &lp).await
+    }
+
+    /// Find namespaced object of kind `K` by its `metadata.uid` in a given (or default) namespace
+    /// Return `None` if there is no such object
+    ///
+    /// UIDs cannot be queried directly, so this lists the metadata of all the objects
+    /// of kind `K` in the namespace and fetches the matching one, which is O(n).
+    ///
+    async fn find_by_uid<K>(
+        &self,
+        uid: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Option<K>>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.namespaced_k::<K>(namespace);
+        find_by_uid(&api, uid, &self.list_params()).await
+    }
+
+    /// Find namespaced object of kind `K` by its `metadata.uid` across all namespaces
+    /// Return `None` if there is no such object
+    ///
+    /// Like [`Self::find_by_uid`] this is O(n), in the number of objects of kind `K`
+    /// in the whole cluster, so prefer narrowing the search to a namespace when known.
+    ///
+    async fn find_by_uid_all_namespaces<K>(&self, uid: &str) -> client::Result<Option<K>>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.api::<K>();
+        find_by_uid(&api, uid, &self.list_params()).await
+    }
+
+    /// List cluster-scoped objects of kind `K`
+    ///
+    async fn list_cluster_k<K>(&self) -> client::Result<Vec<K>>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::ClusterResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.cluster_k::<K>();
+        let lp = self.list_params();
+        api.list(&lp).await.map(|list| list.items)
+    }
+
+    /// Set desired number of `replicas` of named object of kind `K` in a given (or default)
+    /// namespace through its `scale` subresource
+    ///
+    /// Works for any kind serving the subresource (`Deployment`, `StatefulSet`,
+    /// `ReplicaSet`, `ReplicationController` and custom resources declaring it).
+    /// Negative `replicas` are rejected with `BadRequest` error.
+    ///
+    async fn scale<K>(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+        replicas: i32,
+    ) -> client::Result<()>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        if replicas < 0 {
+            let message = format!("Cannot scale {name} to {replicas} replicas");
+            return Err(helper::bad_request(&message));
+        }
+        let patch = api::Patch::Merge(json::json!({ "spec": { "replicas": replicas } }));
+        let api = self.namespaced_k::<K>(namespace);
+        let pp = self.patch_params();
+        api.patch_scale(name, &pp, &patch).await.map(|_| ())
+    }
+
+    /// Get desired and observed number of replicas of named object of kind `K`
+    /// in a given (or default) namespace through its `scale` subresource
+    /// Return `(spec.replicas, status.replicas)`, missing values are reported as `0`
+    ///
+    async fn get_scale<K>(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<(i32, i32)>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.namespaced_k::<K>(namespace);
+        let scale = api.get_scale(name).await?;
+        let desired = scale
+            .spec
+            .and_then(|spec| spec.replicas)
+            .unwrap_or_default();
+        let current = scale
+            .status
+            .map(|status| status.replicas)
+            .unwrap_or_default();
+        Ok((desired, current))
+    }
+
+    /// Set desired number of `replicas` of named deployment in a given (or default) namespace
+    ///
+    async fn scale_deployment(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+        replicas: i32,
+    ) -> client::Result<()> {
+        self.scale::<appsv1::Deployment>(name, namespace, replicas)
+            .await
+    }
+
+    /// Set desired number of `replicas` of named statefulset in a given (or default) namespace
+    ///
+    async fn scale_statefulset(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+        replicas: i32,
+    ) -> client::Result<()> {
+        self.scale::<appsv1::StatefulSet>(name, namespace, replicas)
+            .await
+    }
+
+    /// Server-side apply a heterogeneous list of `objects` with `field_manager`
+    ///
+    /// Each object kind is resolved via API discovery and objects are applied in
+    /// dependency order: `Namespace`s first, then `CustomResourceDefinition`s, then
+    /// the rest, keeping the given order within each group. Before applying the rest,
+    /// applied CRDs are waited for (up to 30 seconds) to become `Established` and
+    /// discovery is re-run, so the custom resources defined by them can be resolved.
+    /// Namespaced objects without `metadata.namespace` go to the default namespace.
+    ///
+    /// The first failure stops applying and is returned as [`BulkApplyError`], along with
+    /// the objects applied so far, unless `continue_on_error` is set, in which case
+    /// the remaining objects are still applied and per-object results are returned
+    /// in the same order as `objects`
+    ///
+    async fn bulk_apply(
+        &self,
+        objects: Vec<api::DynamicObject>,
+        field_manager: &str,
+        continue_on_error: bool,
+    ) -> Result<Vec<client::Result<api::DynamicObject>>, BulkApplyError> {
+        let pp = self.patch_params_with_manager(field_manager);
+        let mut order = (0..objects.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| apply_rank(&objects[index]));
+
+        let client = client_of(self);
+        let mut discovered = HashMap::new();
+        let mut crds = Vec::new();
+        let mut crds_established = false;
+        let mut results = objects.iter().map(|_| None).collect::<Vec<_>>();
+        for index in order {
+            let rank = apply_rank(&objects[index]);
+            if rank > 1 && !crds_established {
+                crds_established = true;
+                if !crds.is_empty() {
+                    wait_for_established(&self.crds(), &crds).await;
+                    discovered.clear();
+                }
+            }
+            let result = apply_dynamic(&client, &objects[index], &pp, &mut discovered).await;
+            match result {
+                Ok(object) if rank == 1 => {
+                    crds.push(object.name_any());
+                    results[index] = Some(Ok(object));
+                }
+                Err(source) if !continue_on_error => {
+                    let applied = results
+                        .into_iter()
+                        .enumerate()
+                        .filter_map(|(index, result)| Some((index, result?.ok()?)))
+                        .collect();
+                    return Err(BulkApplyError { source, applied });
+                }
+                result => results[index] = Some(result),
+            }
+        }
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// Restart all the deployments, statefulsets and daemonsets in a given (or default)
+    /// namespace, like `kubectl rollout restart` does, by setting
+    /// `kubectl.kubernetes.io/restartedAt` annotation of their pod templates
+    ///
+    /// Workloads are patched concurrently, and failing to patch one of them (e.g. when
+    /// the caller is not allowed to) does not stop the others from being restarted.
+    /// Return `Kind/name` of every workload along with the result of its restart.
+    ///
+    async fn restart_namespace_workloads(
+        &self,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<(String, client::Result<()>)>> {
+        let namespace = namespace.into();
+        let (deployments, statefulsets, daemonsets) = futures::try_join!(
+            self.list_deployments(namespace),
+            self.list_statefulsets(namespace),
+            self.list_k::<appsv1::DaemonSet>(namespace),
+        )?;
+        let restarted_at = k8s::openapi::jiff::Timestamp::now().to_string();
+        let patch = api::Patch::Strategic(json::json!({
+            "spec": {
+                "template": {
+                    "metadata": {
+                        "annotations": { "kubectl.kubernetes.io/restartedAt": restarted_at }
+                    }
+                }
+            }
+        }));
+        let pp = self.patch_params();
+
+        let restarts = deployments
+            .iter()
+            .map(|deployment| {
+                let api = self.deployments(namespace);
+                restart_workload(api, deployment.name_any(), &pp, &patch).boxed()
+            })
+            .chain(statefulsets.iter().map(|statefulset| {
+                let api = self.statefulsets(namespace);
+                restart_workload(api, statefulset.name_any(), &pp, &patch).boxed()
+            }))
+            .chain(daemonsets.iter().map(|daemonset| {
+                let api = self.daemonsets(namespace);
+                restart_workload(api, daemonset.name_any(), &pp, &patch).boxed()
+            }))
+            .collect::<Vec<_>>();
+        let restarted = futures::stream::iter(restarts)
+            .buffer_unordered(CONCURRENCY)
+            .collect()
+            .await;
+        Ok(restarted)
+    }
+
+    /// Restart named pod in a given (or default) namespace by deleting it
+    /// (honoring its termination grace period) so that its controller recreates it
+    ///
+    /// When `wait` is given, also wait up to that long until a replacement pod
+    /// controlled by the same controller shows up, failing with `Timeout` error otherwise.
+    /// Pods without a controller are rejected with `BadRequest` error, since nothing
+    /// would bring them back. Return the result of the deletion.
+    ///
+    async fn restart_pod(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+        wait: Option<Duration>,
+    ) -> client::Result<either::Either<corev1::Pod, client::core::Status>> {
+        let namespace = namespace.into();
+        let pods = self.pods(namespace);
+        let pod = pods.get(name).await?;
+        let Some(controller) = controller_of(&pod).map(|owner| owner.uid.clone()) else {
+            let message = format!("Pod {name} has no controller, no replacement would be created");
+            return Err(helper::bad_request(&message));
+        };
+        let known = controlled_pod_uids(&self.list_pods(namespace).await?, &controller);
+
+        let deleted = pods.delete(name, &api::DeleteParams::default()).await?;
+
+        if let Some(wait) = wait {
+            let replacement = async {
+                loop {
+                    let uids = controlled_pod_uids(&self.list_pods(namespace).await?, &controller);
+                    if !uids.is_subset(&known) {
+                        break Ok::<_, client::Error>(());
+                    }
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                }
+            };
+            tokio::time::timeout(wait, replacement)
+                .await
+                .map_err(|_| {
+                    helper::timeout(&format!(
+                        "No replacement for pod {name} observed in {wait:?}"
+                    ))
+                })??;
+        }
+
+        Ok(deleted)
+    }
+
+    /// Wait up to `timeout` until named namespaced object of kind `K` in a given
+    /// (or default) namespace satisfies `condition`, polling it every second
+    /// Return the object that satisfied the condition
+    ///
+    /// An object that does not exist (yet) is polled for as well, so the only
+    /// error reported for the object itself is [`KubeExtError::Timeout`].
+    ///
+    async fn wait_for<K, F>(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+        timeout: Duration,
+        condition: F,
+    ) -> Result<K, KubeExtError>
+    where
+        K: Clone
+            + fmt::Debug
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+        F: Fn(&K) -> bool + Send + Sync,
+    {
+        let namespace = namespace.into();
+        let satisfied = async {
+            loop {
+                if let Some(object) = self.get_k_opt::<K>(name, namespace).await?
+                    && condition(&object)
+                {
+                    break Ok::<_, client::Error>(object);
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        };
+        match tokio::time::timeout(timeout, satisfied).await {
+            Ok(result) => result.map_err(KubeExtError::from),
+            Err(_) => {
+                let kind = K::kind(&Default::default()).into_owned();
+                let message = format!("{kind} {name} did not satisfy condition in {timeout:?}");
+                Err(KubeExtError::Timeout(helper::timeout(&message)))
+            }
+        }
+    }
+
+    /// Wait up to `timeout` until named statefulset in a given (or default) namespace is ready:
+    /// its current generation has been observed, all the replicas are ready
+    /// and the update revision has been rolled out to all of them
+    ///
+    async fn wait_for_statefulset_ready(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+        timeout: Duration,
+    ) -> Result<appsv1::StatefulSet, KubeExtError> {
+        self.wait_for(name, namespace, timeout, statefulset_is_ready)
+            .await
+    }
+
+    /// Wait up to `timeout` until named daemonset in a given (or default) namespace is ready:
+    /// its current generation has been observed and every node that should run it
+    /// runs an updated and ready pod
+    ///
+    async fn wait_for_daemonset_ready(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+        timeout: Duration,
+    ) -> Result<appsv1::DaemonSet, KubeExtError> {
+        self.wait_for(name, namespace, timeout, daemonset_is_ready)
+            .await
+    }
+
+    /// Preview server-side apply of `object` with `field_manager`, like `kubectl diff` does
+    ///
+    /// Performs a dry-run apply (the object kind is resolved via API discovery) and
+    /// compares its result with the live object, excluding `metadata.managedFields`.
+    /// For objects that do not exist yet the whole object is reported as added.
+    ///
+    async fn diff_apply(
+        &self,
+        object: &(impl k8s::openapi::serde::Serialize + Sync),
+        field_manager: &str,
+    ) -> client::Result<JsonDiff> {
+        let object = json::to_value(object)
+            .and_then(json::from_value::<api::DynamicObject>)
+            .map_err(client::Error::SerdeError)?;
+        let (api, name) = dynamic_api(&client_of(self), &object, &mut HashMap::new()).await?;
+        let live = api.get_opt(name).await?;
+        let pp = self.patch_params_with_manager(field_manager).dry_run();
+        let applied = api.patch(name, &pp, &api::Patch::Apply(&object)).await?;
+        let before = live.map(diff::comparable).transpose()?;
+        let after = diff::comparable(applied)?;
+        Ok(JsonDiff::new(before.as_ref(), &after))
+    }
+
+    /// Delete named objects of kind `K` in a given (or default) namespace concurrently
+    /// Objects that are already gone are reported as `Right(Status)`, like
+    /// [`not_found_ok`] does, and results are returned in the same order as `names`
+    ///
+    async fn delete_many<K>(
+        &self,
+        names: &[&str],
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Vec<either::Either<K, client::core::Status>>>
+    where
+        K: Clone
+            + fmt::Debug
+            + Send
+            + k8s::openapi::serde::de::DeserializeOwned
+            + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+        <K as client::Resource>::DynamicType: Default,
+    {
+        let api = self.namespaced_k::<K>(namespace);
+        let dp = self.delete_params();
+        let deletes = names
+            .iter()
+            .map(|name| api.delete(name, &dp))
+            .collect::<Vec<_>>();
+        futures::stream::iter(deletes)
+            .buffered(CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .map(|result| result.or_else(not_found_ok))
+            .collect()
+    }
+
+    /// Get all the pods associated with the deployment
+    /// The logic is based on what `kubectl describe` does
+    ///
+    async fn get_pods_by_deployment_name(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Option<Vec<corev1::Pod>>> {
+        // Get the deployment
+        let Some(deployment) = self.get_deployment_opt(name, namespace).await? else {
+            return Ok(None);
+        };
+        self.get_pods_by_deployment(&deployment).await
+    }
+
+    /// Get the persistent volume claims used by the `pod`, both referenced directly by
+    /// `persistentVolumeClaim` volumes and created for its `ephemeral` volumes
+    /// Claims that no longer exist are skipped
+    ///
+    async fn get_pvcs_for_pod(
+        &self,
+        pod: &corev1::Pod,
+    ) -> client::Result<Vec<corev1::PersistentVolumeClaim>> {
+        let pvcs = self.persistentvolumeclaims(pod.namespace().as_deref());
+        let mut claims = Vec::new();
+        for name in pod_claim_names(pod) {
+            if let Some(claim) = pvcs.get_opt(&name).await? {
+                claims.push(claim);
+            }
+        }
+        Ok(claims)
+    }
+
+    /// Get the persistent volume bound to the `pvc`
+    /// Return `None` if the claim is not bound (or the volume is gone)
+    ///
+    async fn get_pv_for_pvc(
+        &self,
+        pvc: &corev1::PersistentVolumeClaim,
+    ) -> client::Result<Option<corev1::PersistentVolume>> {
+        match pvc
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.volume_name.as_deref())
+        {
+            Some(volume) if !volume.is_empty() => self.persistentvolumes().get_opt(volume).await,
+            _ => Ok(None),
+        }
+    }
+
+    /// Get the persistent volume bound to named claim in a given (or default) namespace
+    /// Return `None` if the claim is not bound (or the volume is gone)
+    ///
+    async fn get_pv_for_pvc_name(
+        &self,
+        name: &str,
+        namespace: impl Into<Option<&str>> + Send,
+    ) -> client::Result<Option<corev1::PersistentVolume>> {
+        let pvc = self.persistentvolumeclaims(namespace).get(name).await?;
+        self.get_pv_for_pvc(&pvc).await
+    }
+
+    /// Get the current (`NewReplicaSet` in `kubectl describe` terms) replicaset
+    /// of the `deployment`, i.e. the one whose pod template matches the deployment's
+    /// Return `None` if there is no such replicaset (e.g. during the rollout start)
+    ///
+    async fn get_current_replicaset(
+        &self,
+        deployment: &appsv1::Deployment,
+    ) -> client::Result<Option<appsv1::ReplicaSet>> {
+        let namespace = deployment.namespace();
+        // Get all its replicas
+        let mut replicasets = self
+            .list_replicasets(namespace.as_deref())
+            .await?
+            .into_iter()
+            .filter(|rs| rs.is_controlled_by(deployment))
+            .collect::<Vec<_>>();
+
+        // Find the `NewReplicaSet`
+        replicasets.sort_by_key(|rs| rs.creation_timestamp());
+        let new = replicasets
+            .into_iter()
+            .find(|rs| match_template_spec_no_hash(rs, deployment));
+
+        Ok(new)
+    }
+
+    /// Get all replicasets owned by the `deployment` except the current one
+    /// (see [`Self::get_current_replicaset`]), sorted by revision (oldest first)
+    /// With `non_empty_only` set replicasets scaled down to zero are skipped
+    ///
+    async fn get_old_replicasets(
+        &self,
+        deployment: &appsv1::Deployment,
+        non_empty_only: bool,
+    ) -> client::Result<Vec<appsv1::ReplicaSet>> {
+        let current = self
+            .get_current_replicaset(deployment)
+            .await?
+            .and_then(|rs| rs.uid());
+        let mut replicasets = self
+            .list_replicasets(deployment.namespace().as_deref())
+            .await?
+            .into_iter()
+            .filter(|rs| rs.is_controlled_by(deployment))
+            .filter(|rs| rs.uid() != current)
+            .filter(|rs| !non_empty_only || !replicaset_is_empty(rs))
+            .collect::<Vec<_>>();
+        replicasets.sort_by_key(replicaset_revision);
+        Ok(replicasets)
+    }
+
+    /// Delete old replicasets of the `deployment` beyond the `keep` most recent ones,
+    /// the way the deployment controller honors `revisionHistoryLimit`
+    /// The current replicaset and replicasets that still have replicas are never deleted
+    /// Return the names of the deleted replicasets
+    ///
+    async fn prune_replica_sets(
+        &self,
+        deployment: &appsv1::Deployment,
+        keep: usize,
+    ) -> client::Result<Vec<String>> {
+        let old = self.get_old_replicasets(deployment, false).await?;
+        let excess = old.len().saturating_sub(keep);
+        let replicasets = self.replicasets(deployment.namespace().as_deref());
+        let dp = self.background_delete();
+        let mut deleted = Vec::new();
+        for rs in old.iter().take(excess).filter(|rs| replicaset_is_empty(rs)) {
+            let name = rs.name_any();
+            replicasets.delete(&name, &dp).await.or_else(not_found_ok)?;
+            deleted.push(name);
+        }
+        Ok(deleted)
+    }
+
+    /// Get all the pods associated with the `deployment`
+    /// The logic is based on what `kubectl describe` does
+    ///
+    async fn get_pods_by_deployment(
+        &self,
+        deployment: &appsv1::Deployment,
+    ) -> client::Result<Option<Vec<corev1::Pod>>> {
+        let Some(new) = self.get_current_replicaset(deployment).await? else {
+            return Ok(None);
+        };
+
+        // Find all the Pods controlled by this ReplicaSet
+        let pods = self
+            .list_pods(deployment.namespace().as_deref())
+            .await?
+            .into_iter()
+            .filter(|pod| pod.is_controlled_by(&new))
+            .collect();
+
+        Ok(Some(pods))
+    }
+
+    /// Get all the pods in the namespace of the `deployment` matching its `spec.selector`,
+    /// regardless of owner references
+    ///
+    /// Unlike [`Self::get_pods_by_deployment`], which follows ownership through the current
+    /// replicaset, this also returns pods of older replicasets and orphaned pods
+    /// that still match the selector (and would be adopted by the deployment).
+    /// A deployment without a selector matches no pods.
+    ///
+    async fn get_pods_by_deployment_selector(
+        &self,
+        deployment: &appsv1::Deployment,
+    ) -> Result<Vec<corev1::Pod>, KubeExtError> {
+        let Some(selector) = deployment.spec.as_ref().map(|spec| &spec.selector) else {
+            return Ok(vec![]);
+        };
+        let labels =
+            LabelSelector::from(selector.match_labels.clone().unwrap_or_default()).build()?;
+        let lp = self.list_params().labels(&labels);
+        let mut pods = self
+            .pods(deployment.namespace().as_deref())
+            .list(&lp)
+            .await?
+            .items;
+        pods.retain(|pod| selector_matches(selector, pod.labels()));
+        Ok(pods)
+    }
+
+    /// Get all the pods controlled by a given statefulset
+    async fn get_pods_by_statefulset(
+        &self,
+        statefulset: &appsv1::StatefulSet,
+    ) -> client::Result<Option<Vec<corev1::Pod>>> {
+        let namespace = statefulset.namespace();
+        let pods = if let Some(revision) = statefulset.current_revision() {
+            let controller_revision = format!(
+                "{}={}",
+                k8s::label::CONTROLLER_REVISION_HASH_LABEL_KEY,
+                revision
+            );
+            let lp = self.list_params().labels(&controller_revision);
+            self.pods(namespace.as_deref()).list(&lp).await?.items
+        } else {
+            vec![]
+        };
+        Ok(Some(pods))
+    }
+
+    /// Get all the pods controlled by the replication controller `rc`
+    ///
+    async fn get_pods_by_replicationcontroller(
+        &self,
+        rc: &corev1::ReplicationController,
+    ) -> client::Result<Vec<corev1::Pod>> {
+        let pods = self
+            .list_pods(rc.namespace().as_deref())
+            .await?
+            .into_iter()
+            .filter(|pod| pod.is_controlled_by(rc))
+            .collect();
+        Ok(pods)
+    }
+
+    async fn get_pods_by_statefulset_name(
         &self,
         name: &str,
         namespace: impl Into<Option<&str>> + Send,
@@ -346,6 +3215,78 @@ pub trait KubeClientExt2: KubeClientExt {
         };
         self.get_pods_by_statefulset(&statefulset).await
     }
+
+    /// Check whether the API server reports itself ready (`/readyz`)
+    ///
+    /// Connection failures and unhealthy responses are reported as `false`, while
+    /// authentication and authorization failures (`401` and `403`) are returned as errors.
+    ///
+    async fn ping(&self) -> client::Result<bool> {
+        match raw_get(&client_of(self), "/readyz").await {
+            Ok(text) => Ok(text.trim() == "ok"),
+            Err(client::Error::Api(status)) if matches!(status.code, 401 | 403) => {
+                Err(client::Error::Api(status))
+            }
+            Err(
+                client::Error::Api(_) | client::Error::HyperError(_) | client::Error::Service(_),
+            ) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Get health of the individual API server liveness checks (e.g. `ping`, `etcd`,
+    /// `poststarthook/...`) from verbose `/livez` output
+    ///
+    async fn livez_components(&self) -> client::Result<BTreeMap<String, bool>> {
+        let text = match raw_get(&client_of(self), "/livez?verbose").await {
+            Ok(text) => text,
+            // Failed checks are reported with 500 and the same verbose body
+            Err(client::Error::Api(status)) if status.code == 500 => status.message,
+            Err(err) => return Err(err),
+        };
+        let components = text
+            .lines()
+            .filter_map(|line| {
+                let (healthy, check) = if let Some(check) = line.strip_prefix("[+]") {
+                    (true, check)
+                } else {
+                    (false, line.strip_prefix("[-]")?)
+                };
+                let name = check.split_whitespace().next()?;
+                Some((name.to_string(), healthy))
+            })
+            .collect();
+        Ok(components)
+    }
+
+    /// Run `f` retrying transient failures according to `policy`
+    /// Non-retryable errors are returned immediately
+    ///
+    async fn with_retries<T, F, Fut>(&self, policy: RetryPolicy, f: F) -> client::Result<T>
+    where
+        T: Send,
+        F: Fn() -> Fut + Send + Sync,
+        Fut: Future<Output = client::Result<T>> + Send,
+    {
+        policy.retry(f).await
+    }
+
+    /// Run `f` and retry it once if the API server throttled the request (429),
+    /// after waiting for the delay it suggested (capped at [`MAX_RETRY_AFTER`]),
+    /// or for one second if it did not suggest any
+    /// Any other outcome is returned as is
+    ///
+    /// The `Retry-After` response header is not visible through [`client::Error`],
+    /// so the suggested delay is taken from `retryAfterSeconds` of the returned `Status`.
+    ///
+    async fn with_throttle_retry<T, F, Fut>(&self, f: F) -> client::Result<T>
+    where
+        T: Send,
+        F: Fn() -> Fut + Send + Sync,
+        Fut: Future<Output = client::Result<T>> + Send,
+    {
+        retry::retry_throttled(MAX_RETRY_AFTER, f).await
+    }
 }
 
 impl KubeClientExt2 for client::Client {}
@@ -364,6 +3305,792 @@ fn remove_hash(template: &corev1::PodTemplateSpec) -> corev1::PodTemplateSpec {
     template
 }
 
+/// All the backends of the `ingress`, default backend first
+fn ingress_backends(
+    ingress: &networkingv1::Ingress,
+) -> impl Iterator<Item = &networkingv1::IngressBackend> {
+    let spec = ingress.spec.as_ref();
+    let default_backend = spec.and_then(|spec| spec.default_backend.as_ref());
+    let path_backends = spec
+        .and_then(|spec| spec.rules.as_deref())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|rule| rule.http.as_ref())
+        .flat_map(|http| &http.paths)
+        .map(|path| &path.backend);
+    default_backend.into_iter().chain(path_backends)
+}
+
+/// Service backends of the `ingress` labeled by `host/path` (or `default`),
+/// each with the name of the service
+fn ingress_service_backends(ingress: &networkingv1::Ingress) -> Vec<(String, &str)> {
+    let spec = ingress.spec.as_ref();
+    let default_backend = spec
+        .and_then(|spec| spec.default_backend.as_ref())
+        .map(|backend| ("default".to_string(), backend));
+    let path_backends = spec
+        .and_then(|spec| spec.rules.as_deref())
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|rule| {
+            let host = rule.host.as_deref().unwrap_or("*");
+            rule.http
+                .iter()
+                .flat_map(|http| &http.paths)
+                .map(move |path| {
+                    let path_str = path.path.as_deref().unwrap_or("/");
+                    (format!("{host}{path_str}"), &path.backend)
+                })
+        });
+    default_backend
+        .into_iter()
+        .chain(path_backends)
+        .filter_map(|(label, backend)| Some((label, backend_service_name(backend)?)))
+        .collect()
+}
+
+fn backend_service_name(backend: &networkingv1::IngressBackend) -> Option<&str> {
+    backend
+        .service
+        .as_ref()
+        .map(|service| service.name.as_str())
+}
+
+/// Whether the current `generation` of `metadata` has been observed by its controller
+fn generation_observed(metadata: &metav1::ObjectMeta, observed: Option<i64>) -> bool {
+    observed >= metadata.generation
+}
+
+fn statefulset_is_ready(statefulset: &appsv1::StatefulSet) -> bool {
+    let replicas = statefulset
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.replicas)
+        .unwrap_or(1);
+    statefulset.status.as_ref().is_some_and(|status| {
+        generation_observed(&statefulset.metadata, status.observed_generation)
+            && status.ready_replicas.unwrap_or_default() == replicas
+            && status.update_revision.is_some()
+            && status.update_revision == status.current_revision
+    })
+}
+
+fn daemonset_is_ready(daemonset: &appsv1::DaemonSet) -> bool {
+    daemonset.status.as_ref().is_some_and(|status| {
+        generation_observed(&daemonset.metadata, status.observed_generation)
+            && status.number_ready == status.desired_number_scheduled
+            && status.updated_number_scheduled.unwrap_or_default()
+                == status.desired_number_scheduled
+    })
+}
+
+/// Patch `var` into the env of a `container` of named deployment's pod template
+async fn patch_container_env(
+    deployments: &api::Api<appsv1::Deployment>,
+    name: &str,
+    container: Option<&str>,
+    var: json::Value,
+    pp: &api::PatchParams,
+) -> client::Result<appsv1::Deployment> {
+    let deployment = deployments.get(name).await?;
+    let container = deployment_container(&deployment, container)?;
+    let patch = json::json!({
+        "spec": {
+            "template": {
+                "spec": {
+                    "containers": [{ "name": container.name, "env": [var] }]
+                }
+            }
+        }
+    });
+    deployments
+        .patch(name, pp, &api::Patch::Strategic(patch))
+        .await
+}
+
+fn deployment_container<'a>(
+    deployment: &'a appsv1::Deployment,
+    container: Option<&str>,
+) -> client::Result<&'a corev1::Container> {
+    let containers = deployment.containers().unwrap_or_default();
+    let found = match container {
+        Some(container) => containers.iter().find(|item| item.name == container),
+        None => containers.first(),
+    };
+    found.ok_or_else(|| {
+        let message = format!(
+            "Container {} not found in deployment {}",
+            container.unwrap_or_default(),
+            deployment.name_any()
+        );
+        helper::bad_request(&message)
+    })
+}
+
+/// Revision recorded by the deployment controller in `deployment.kubernetes.io/revision`
+fn replicaset_revision(rs: &appsv1::ReplicaSet) -> i64 {
+    rs.annotations()
+        .get("deployment.kubernetes.io/revision")
+        .and_then(|revision| revision.parse().ok())
+        .unwrap_or_default()
+}
+
+fn replicaset_is_empty(rs: &appsv1::ReplicaSet) -> bool {
+    rs.spec_replicas().unwrap_or_default() == 0 && rs.status_replicas().unwrap_or_default() == 0
+}
+
+/// Names of the claims used by pod volumes, ephemeral volume claims
+/// are named `<pod name>-<volume name>`
+fn pod_claim_names(pod: &corev1::Pod) -> Vec<String> {
+    let pod_name = pod.name_any();
+    pod.spec
+        .iter()
+        .flat_map(|spec| spec.volumes.iter().flatten())
+        .filter_map(|volume| {
+            if let Some(claim) = &volume.persistent_volume_claim {
+                Some(claim.claim_name.clone())
+            } else if volume.ephemeral.is_some() {
+                Some(format!("{pod_name}-{}", volume.name))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn rules_match(
+    rules: Option<&[admissionregistrationv1::RuleWithOperations]>,
+    gvk: &api::GroupVersionKind,
+    plural: &str,
+) -> bool {
+    let matches = |values: Option<&Vec<String>>, value: &str| {
+        values
+            .into_iter()
+            .flatten()
+            .any(|item| item == "*" || item == value)
+    };
+    rules.unwrap_or_default().iter().any(|rule| {
+        matches(rule.api_groups.as_ref(), &gvk.group)
+            && matches(rule.api_versions.as_ref(), &gvk.version)
+            && rule
+                .resources
+                .iter()
+                .flatten()
+                .any(|resource| resource == "*" || resource == "*/*" || resource == plural)
+    })
+}
+
+fn controlled_pod_uids(pods: &[corev1::Pod], controller: &str) -> BTreeSet<String> {
+    pods.iter()
+        .filter(|pod| controller_of(*pod).is_some_and(|owner| owner.uid == controller))
+        .filter_map(|pod| pod.uid())
+        .collect()
+}
+
+/// Identity of `object` across relists, its `uid` (or `namespace/name` if not set)
+fn object_key(object: &impl client::ResourceExt) -> String {
+    object.uid().unwrap_or_else(|| {
+        format!(
+            "{}/{}",
+            object.namespace().unwrap_or_default(),
+            object.name_any()
+        )
+    })
+}
+
+/// Underlying `kube::Client` of `ext`, for dynamic (discovery driven) requests
+fn client_of(ext: &impl KubeClientExt) -> client::Client {
+    ext.api::<corev1::Namespace>().into_client()
+}
+
+/// Owner reference of the managing controller, if any
+fn controller_of(object: &impl client::ResourceExt) -> Option<&metav1::OwnerReference> {
+    object
+        .owner_references()
+        .iter()
+        .find(|owner| owner.controller == Some(true))
+}
+
+/// Whether RBAC `subject` applies to named service account in `namespace`
+fn subject_is_serviceaccount(subject: &rbacv1::Subject, name: &str, namespace: &str) -> bool {
+    match subject.kind.as_str() {
+        "ServiceAccount" => subject.name == name && subject.namespace.as_deref() == Some(namespace),
+        "User" => subject.name == format!("system:serviceaccount:{namespace}:{name}"),
+        "Group" => {
+            subject.name == "system:serviceaccounts"
+                || subject.name == format!("system:serviceaccounts:{namespace}")
+        }
+        _ => false,
+    }
+}
+
+/// When the `event` has been (last) observed, falling back to its creation time
+fn event_timestamp(event: &corev1::Event) -> Option<k8s::openapi::jiff::Timestamp> {
+    event
+        .last_timestamp
+        .as_ref()
+        .map(|time| time.0)
+        .or_else(|| event.event_time.as_ref().map(|time| time.0))
+        .or_else(|| {
+            event
+                .metadata
+                .creation_timestamp
+                .as_ref()
+                .map(|time| time.0)
+        })
+}
+
+/// Node address reachable from outside, preferring `ExternalIP` over `InternalIP`
+fn node_address(node: &corev1::Node) -> Option<&str> {
+    let addresses = node.status.as_ref()?.addresses.as_deref()?;
+    let address_of = |r#type: &str| {
+        addresses
+            .iter()
+            .find(|address| address.type_ == r#type)
+            .map(|address| address.address.as_str())
+    };
+    address_of("ExternalIP").or_else(|| address_of("InternalIP"))
+}
+
+/// Turn "forbidden" count failure into unknown count
+async fn permitted_count<K>(
+    count: impl Future<Output = client::Result<usize>>,
+) -> client::Result<Option<usize>>
+where
+    K: client::Resource,
+    <K as client::Resource>::DynamicType: Default,
+{
+    match count.await {
+        Ok(count) => Ok(Some(count)),
+        Err(client::Error::Api(status)) if status.is_forbidden() => {
+            let kind = K::kind(&K::DynamicType::default()).into_owned();
+            tracing::debug!(
+                kind,
+                message = status.message,
+                "not allowed to count, skipping"
+            );
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Issue raw `GET` request for non-resource `path` and return the response body
+async fn raw_get(client: &client::Client, path: &str) -> client::Result<String> {
+    let request = http::Request::get(path)
+        .body(vec![])
+        .map_err(client::Error::HttpError)?;
+    client.request_text(request).await
+}
+
+/// Apply restart `patch` to named workload, return its `Kind/name` with the patch result
+async fn restart_workload<K>(
+    api: api::Api<K>,
+    name: String,
+    pp: &api::PatchParams,
+    patch: &api::Patch<json::Value>,
+) -> (String, client::Result<()>)
+where
+    K: Clone
+        + fmt::Debug
+        + k8s::openapi::serde::de::DeserializeOwned
+        + client::Resource<DynamicType = ()>,
+{
+    let result = api.patch(&name, pp, patch).await.map(|_| ());
+    (format!("{}/{name}", K::kind(&())), result)
+}
+
+/// Turn "forbidden" list failure into an empty list
+async fn permitted<K>(list: impl Future<Output = client::Result<Vec<K>>>) -> client::Result<Vec<K>>
+where
+    K: client::Resource,
+    <K as client::Resource>::DynamicType: Default,
+{
+    match list.await {
+        Err(client::Error::Api(status)) if status.is_forbidden() => {
+            let kind = K::kind(&K::DynamicType::default()).into_owned();
+            tracing::debug!(
+                kind,
+                message = status.message,
+                "not allowed to list, skipping"
+            );
+            Ok(vec![])
+        }
+        other => other,
+    }
+}
+
+fn tls_secret(name: &str, cert_pem: &[u8], key_pem: &[u8]) -> client::Result<corev1::Secret> {
+    if cert_pem.is_empty() {
+        return Err(helper::bad_request("TLS certificate must not be empty"));
+    }
+    if key_pem.is_empty() {
+        return Err(helper::bad_request("TLS private key must not be empty"));
+    }
+    let data = [
+        (
+            corev1::Secret::TLS_CERT_KEY,
+            k8s::ByteString(cert_pem.to_vec()),
+        ),
+        (
+            corev1::Secret::TLS_PRIVATE_KEY_KEY,
+            k8s::ByteString(key_pem.to_vec()),
+        ),
+    ];
+    let secret = corev1::Secret::new(name)
+        .r#type(corev1::Secret::SECRET_TYPE_TLS)
+        .data(data);
+    Ok(secret)
+}
+
+fn opaque_secret(name: &str, data: BTreeMap<String, String>) -> client::Result<corev1::Secret> {
+    if data.keys().any(String::is_empty) {
+        return Err(helper::bad_request("Secret data keys must not be empty"));
+    }
+    let secret = corev1::Secret::opaque(name).string_data(data);
+    Ok(secret)
+}
+
+/// Render `~/.docker/config.json` style content for given `(registry, username, password)` triples
+fn docker_config_json<'a>(auths: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>) -> String {
+    let auths = auths
+        .into_iter()
+        .map(|(registry, username, password)| {
+            let auth = base64::prelude::BASE64_STANDARD.encode(format!("{username}:{password}"));
+            let entry = json::json!({
+                "username": username,
+                "password": password,
+                "auth": auth,
+            });
+            (registry.to_string(), entry)
+        })
+        .collect::<json::Map<_, _>>();
+    json::json!({ "auths": auths }).to_string()
+}
+
+fn apply_rank(object: &api::DynamicObject) -> u8 {
+    match object.types.as_ref().map(|types| types.kind.as_str()) {
+        Some("Namespace") => 0,
+        Some("CustomResourceDefinition") => 1,
+        _ => 2,
+    }
+}
+
+async fn apply_dynamic(
+    client: &client::Client,
+    object: &api::DynamicObject,
+    pp: &api::PatchParams,
+    discovered: &mut HashMap<api::GroupVersionKind, (api::ApiResource, discovery::ApiCapabilities)>,
+) -> client::Result<api::DynamicObject> {
+    let (api, name) = dynamic_api(client, object, discovered).await?;
+    api.patch(name, pp, &api::Patch::Apply(object)).await
+}
+
+/// Wait up to [`CRD_ESTABLISHED_TIMEOUT`] until all the named `crds` are `Established`
+/// CRDs that do not get there are only logged, as the custom resources defined by them
+/// fail to apply on their own
+async fn wait_for_established(
+    api: &api::Api<apiextensionsv1::CustomResourceDefinition>,
+    crds: &[String],
+) {
+    let established = async {
+        for name in crds {
+            loop {
+                let crd = api.get_opt(name).await?;
+                let established = crd
+                    .and_then(|crd| crd.status)
+                    .and_then(|status| status.conditions)
+                    .into_iter()
+                    .flatten()
+                    .any(|condition| {
+                        condition.type_ == "Established" && condition.status == "True"
+                    });
+                if established {
+                    break;
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+        Ok::<_, client::Error>(())
+    };
+    match tokio::time::timeout(CRD_ESTABLISHED_TIMEOUT, established).await {
+        Ok(Ok(())) => {}
+        Ok(Err(err)) => tracing::warn!(%err, "failed to check CRDs, applying anyway"),
+        Err(_) => tracing::warn!(?crds, "CRDs not established in time, applying anyway"),
+    }
+}
+
+/// Resolve `Api` handle for `object` through API discovery (caching the results
+/// in `discovered`), along with the object name
+async fn dynamic_api<'a>(
+    client: &client::Client,
+    object: &'a api::DynamicObject,
+    discovered: &mut HashMap<api::GroupVersionKind, (api::ApiResource, discovery::ApiCapabilities)>,
+) -> client::Result<(api::Api<api::DynamicObject>, &'a str)> {
+    let Some(name) = object.metadata.name.as_deref() else {
+        return Err(helper::bad_request("Object is missing metadata.name"));
+    };
+    let types = object.types.as_ref().ok_or_else(|| {
+        helper::bad_request(&format!("Object {name} is missing apiVersion or kind"))
+    })?;
+    let namespace = object.metadata.namespace.as_deref();
+    let api = resolve_api(client, types, namespace, discovered).await?;
+    Ok((api, name))
+}
+
+/// Resolve `Api` handle for objects of `types` (in `namespace`, unless cluster scoped)
+/// through API discovery, caching the results in `discovered`
+async fn resolve_api(
+    client: &client::Client,
+    types: &api::TypeMeta,
+    namespace: Option<&str>,
+    discovered: &mut HashMap<api::GroupVersionKind, (api::ApiResource, discovery::ApiCapabilities)>,
+) -> client::Result<api::Api<api::DynamicObject>> {
+    let gvk = api::GroupVersionKind::try_from(types)
+        .map_err(|err| helper::bad_request(&err.to_string()))?;
+    let (resource, capabilities) = match discovered.get(&gvk) {
+        Some(found) => found.clone(),
+        None => {
+            let found = discovery::pinned_kind(client, &gvk).await?;
+            discovered.insert(gvk, found.clone());
+            found
+        }
+    };
+    let api = match capabilities.scope {
+        discovery::Scope::Cluster => api::Api::all_with(client.clone(), &resource),
+        discovery::Scope::Namespaced => {
+            let namespace = namespace.unwrap_or_else(|| client.default_namespace());
+            api::Api::namespaced_with(client.clone(), namespace, &resource)
+        }
+    };
+    Ok(api)
+}
+
+/// Count objects served by `api` listing a single object and relying on
+/// `metadata.remainingItemCount` (see [`KubeClientExt2::count_k`])
+async fn count_objects<K>(api: &api::Api<K>, lp: &api::ListParams) -> client::Result<usize>
+where
+    K: Clone + fmt::Debug + k8s::openapi::serde::de::DeserializeOwned,
+{
+    let (fetched, remaining, more) = {
+        let page = api.list_metadata(&lp.clone().limit(1)).await?;
+        let more = page
+            .metadata
+            .continue_
+            .is_some_and(|token| !token.is_empty());
+        (page.items.len(), page.metadata.remaining_item_count, more)
+    };
+    let count = match remaining {
+        Some(remaining) => fetched + usize::try_from(remaining).unwrap_or_default(),
+        None if more => api.list_metadata(lp).await?.items.len(),
+        None => fetched,
+    };
+    Ok(count)
+}
+
+/// `Api` handle for instances of the custom resource defined by `crd`,
+/// in `namespace` or across all namespaces (see [`KubeClientExt2::get_crd_instances`])
+fn crd_api(
+    client: &client::Client,
+    crd: &apiextensionsv1::CustomResourceDefinition,
+    namespace: Option<&str>,
+) -> client::Result<api::Api<api::DynamicObject>> {
+    let served = || crd.spec.versions.iter().filter(|version| version.served);
+    let Some(version) = served()
+        .find(|version| version.storage)
+        .or_else(|| served().next())
+    else {
+        let message = format!("CRD {} has no served versions", crd.name_any());
+        return Err(helper::bad_request(&message));
+    };
+    let gvk = api::GroupVersionKind::gvk(&crd.spec.group, &version.name, &crd.spec.names.kind);
+    let resource = api::ApiResource::from_gvk_with_plural(&gvk, &crd.spec.names.plural);
+    let api = match namespace {
+        Some(namespace) if crd.spec.scope == "Namespaced" => {
+            api::Api::namespaced_with(client.clone(), namespace, &resource)
+        }
+        _ => api::Api::all_with(client.clone(), &resource),
+    };
+    Ok(api)
+}
+
+/// Fetch the object whose `metadata.uid` is `uid`, looking it up in the metadata list of `api`
+async fn find_by_uid<K>(
+    api: &api::Api<K>,
+    uid: &str,
+    lp: &api::ListParams,
+) -> client::Result<Option<K>>
+where
+    K: Clone
+        + fmt::Debug
+        + k8s::openapi::serde::de::DeserializeOwned
+        + client::Resource<Scope = k8s::openapi::NamespaceResourceScope>,
+    <K as client::Resource>::DynamicType: Default,
+{
+    let Some((name, namespace)) = api
+        .list_metadata(lp)
+        .await?
+        .items
+        .into_iter()
+        .find(|object| object.metadata.uid.as_deref() == Some(uid))
+        .map(|object| {
+            (
+                object.name_any(),
+                object.metadata.namespace.unwrap_or_default(),
+            )
+        })
+    else {
+        return Ok(None);
+    };
+    let object = api::Api::<K>::namespaced(api.clone().into_client(), &namespace)
+        .get_opt(&name)
+        .await?
+        .filter(|object| object.meta().uid.as_deref() == Some(uid));
+    Ok(object)
+}
+
+/// Fetch object referenced by `owner` of a dependent in `namespace`
+/// Return `None` if it is gone (or has been replaced by an object with different uid)
+async fn get_owner_dynamic(
+    client: &client::Client,
+    owner: &metav1::OwnerReference,
+    namespace: Option<&str>,
+    discovered: &mut HashMap<api::GroupVersionKind, (api::ApiResource, discovery::ApiCapabilities)>,
+) -> client::Result<Option<api::DynamicObject>> {
+    let types = api::TypeMeta {
+        api_version: owner.api_version.clone(),
+        kind: owner.kind.clone(),
+    };
+    let api = resolve_api(client, &types, namespace, discovered).await?;
+    let object = api
+        .get_opt(&owner.name)
+        .await?
+        .filter(|object| object.metadata.uid.as_ref() == Some(&owner.uid));
+    Ok(object)
+}
+
+/// Metadata for a copy of an object, named `name`, without any of the server managed fields
+fn copied_metadata(metadata: metav1::ObjectMeta, name: &str) -> metav1::ObjectMeta {
+    let annotations = metadata.annotations.map(|mut annotations| {
+        annotations.remove("kubectl.kubernetes.io/last-applied-configuration");
+        annotations
+    });
+    metav1::ObjectMeta {
+        name: Some(name.to_string()),
+        labels: metadata.labels,
+        annotations: annotations.filter(|annotations| !annotations.is_empty()),
+        ..Default::default()
+    }
+}
+
+fn copied_configmap(configmap: corev1::ConfigMap, name: &str) -> corev1::ConfigMap {
+    corev1::ConfigMap {
+        metadata: copied_metadata(configmap.metadata, name),
+        ..configmap
+    }
+}
+
+fn copied_secret(secret: corev1::Secret, name: &str) -> corev1::Secret {
+    corev1::Secret {
+        metadata: copied_metadata(secret.metadata, name),
+        ..secret
+    }
+}
+
+fn pod_container<'a>(pod: &'a corev1::Pod, name: &str) -> Option<&'a corev1::Container> {
+    let spec = pod.spec.as_ref()?;
+    spec.containers
+        .iter()
+        .chain(spec.init_containers.iter().flatten())
+        .find(|container| container.name == name)
+}
+
+/// Names of configmaps and secrets referenced by container environment
+fn env_sources(container: &corev1::Container) -> (BTreeSet<&str>, BTreeSet<&str>) {
+    let mut configmaps = BTreeSet::new();
+    let mut secrets = BTreeSet::new();
+    for source in container.env_from.iter().flatten() {
+        if let Some(configmap) = &source.config_map_ref {
+            configmaps.insert(configmap.name.as_str());
+        }
+        if let Some(secret) = &source.secret_ref {
+            secrets.insert(secret.name.as_str());
+        }
+    }
+    for source in container
+        .env
+        .iter()
+        .flatten()
+        .filter_map(|var| var.value_from.as_ref())
+    {
+        if let Some(configmap) = &source.config_map_key_ref {
+            configmaps.insert(configmap.name.as_str());
+        }
+        if let Some(secret) = &source.secret_key_ref {
+            secrets.insert(secret.name.as_str());
+        }
+    }
+    (configmaps, secrets)
+}
+
+fn container_env(
+    container: &corev1::Container,
+    configmaps: &BTreeMap<&str, BTreeMap<String, String>>,
+    secrets: &BTreeMap<&str, BTreeMap<String, String>>,
+) -> BTreeMap<String, String> {
+    let mut env = BTreeMap::new();
+    for source in container.env_from.iter().flatten() {
+        let prefix = source.prefix.as_deref().unwrap_or_default();
+        let data = if let Some(configmap) = &source.config_map_ref {
+            configmaps.get(configmap.name.as_str())
+        } else if let Some(secret) = &source.secret_ref {
+            secrets.get(secret.name.as_str())
+        } else {
+            None
+        };
+        for (key, value) in data.into_iter().flatten() {
+            env.insert(format!("{prefix}{key}"), value.clone());
+        }
+    }
+    for var in container.env.iter().flatten() {
+        let value = match &var.value_from {
+            Some(source) => env_var_source_value(source, configmaps, secrets),
+            None => Some(var.value.clone().unwrap_or_default()),
+        };
+        if let Some(value) = value {
+            env.insert(var.name.clone(), value);
+        }
+    }
+    env
+}
+
+fn env_var_source_value(
+    source: &corev1::EnvVarSource,
+    configmaps: &BTreeMap<&str, BTreeMap<String, String>>,
+    secrets: &BTreeMap<&str, BTreeMap<String, String>>,
+) -> Option<String> {
+    if let Some(selector) = &source.config_map_key_ref {
+        configmaps
+            .get(selector.name.as_str())?
+            .get(&selector.key)
+            .cloned()
+    } else if let Some(selector) = &source.secret_key_ref {
+        secrets
+            .get(selector.name.as_str())?
+            .get(&selector.key)
+            .cloned()
+    } else if let Some(selector) = &source.field_ref {
+        Some(format!("<fieldRef:{}>", selector.field_path))
+    } else {
+        source
+            .resource_field_ref
+            .as_ref()
+            .map(|selector| format!("<resourceFieldRef:{}>", selector.resource))
+    }
+}
+
+fn secret_text_data(secret: corev1::Secret) -> BTreeMap<String, String> {
+    secret
+        .data
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| (key, String::from_utf8_lossy(&value.0).into_owned()))
+        .collect()
+}
+
 fn labels_mut(template: &mut corev1::PodTemplateSpec) -> Option<&mut BTreeMap<String, String>> {
     template.metadata.as_mut()?.labels.as_mut()
 }
+
+#[cfg(test)]
+mod tests {
+    use client::client::Body;
+    use http::Request;
+    use http::Response;
+    use k8s::openapi::ClusterResourceScope;
+
+    use super::*;
+
+    /// Minimal cluster-scoped kind, unknown to `k8s-openapi`
+    #[derive(Clone, Debug, serde::Deserialize)]
+    struct Fake {
+        metadata: metav1::ObjectMeta,
+    }
+
+    impl k8s::openapi::Resource for Fake {
+        const API_VERSION: &'static str = "example.com/v1";
+        const GROUP: &'static str = "example.com";
+        const KIND: &'static str = "Fake";
+        const VERSION: &'static str = "v1";
+        const URL_PATH_SEGMENT: &'static str = "fakes";
+        type Scope = ClusterResourceScope;
+    }
+
+    impl k8s::openapi::Metadata for Fake {
+        type Ty = metav1::ObjectMeta;
+
+        fn metadata(&self) -> &Self::Ty {
+            &self.metadata
+        }
+
+        fn metadata_mut(&mut self) -> &mut Self::Ty {
+            &mut self.metadata
+        }
+    }
+
+    /// Client backed by a mock service answering a single `GET` of `path`
+    /// with `status` and `body`
+    fn mock_client(path: &'static str, status: u16, body: json::Value) -> client::Client {
+        let (service, mut handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        tokio::spawn(async move {
+            let (request, send) = handle.next_request().await.expect("request");
+            assert_eq!(request.method(), http::Method::GET);
+            assert_eq!(request.uri().path(), path);
+            let response = Response::builder()
+                .status(status)
+                .body(Body::from(json::to_vec(&body).unwrap()))
+                .unwrap();
+            send.send_response(response);
+        });
+        client::Client::new(service, "default")
+    }
+
+    fn not_found(name: &str) -> json::Value {
+        json::json!({
+            "apiVersion": "v1",
+            "kind": "Status",
+            "status": "Failure",
+            "message": format!("fakes.example.com \"{name}\" not found"),
+            "reason": "NotFound",
+            "code": 404
+        })
+    }
+
+    #[tokio::test]
+    async fn get_cluster_k_opt_found() {
+        let body = json::json!({
+            "apiVersion": "example.com/v1",
+            "kind": "Fake",
+            "metadata": { "name": "one", "uid": "1234" }
+        });
+        let client = mock_client("/apis/example.com/v1/fakes/one", 200, body);
+        let fake = client.get_cluster_k_opt::<Fake>("one").await.unwrap();
+        let fake = fake.expect("found");
+        assert_eq!(fake.metadata.name.as_deref(), Some("one"));
+        assert_eq!(fake.metadata.uid.as_deref(), Some("1234"));
+    }
+
+    #[tokio::test]
+    async fn get_cluster_k_opt_not_found() {
+        let client = mock_client("/apis/example.com/v1/fakes/two", 404, not_found("two"));
+        let fake = client.get_cluster_k_opt::<Fake>("two").await.unwrap();
+        assert!(fake.is_none());
+    }
+
+    #[tokio::test]
+    async fn get_cluster_k_not_found() {
+        let client = mock_client("/apis/example.com/v1/fakes/two", 404, not_found("two"));
+        let err = client.get_cluster_k::<Fake>("two").await.unwrap_err();
+        assert!(KubeExtError::from(err).is_not_found());
+    }
+}
@@ -0,0 +1,40 @@
+use std::fmt;
+
+use super::*;
+
+/// Lookup helpers for any `Api<K>` handle, complementing its inherent `get` and `get_opt`
+///
+/// Implemented for `kube::Api<K>`, so code holding its own `Api` handles
+/// can use the same lookups `KubeClientExt2` relies on internally.
+///
+/// ```no_run
+/// # async fn example(client: kube_client::Client) -> kube_client::Result<()> {
+/// use k8s_openapi_ext::corev1;
+/// use kube_client_ext::ApiGetExt;
+///
+/// let pods = kube_client::Api::<corev1::Pod>::namespaced(client, "default");
+/// if pods.exists("my-pod").await? {
+///     let pod = pods.get("my-pod").await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+#[async_trait::async_trait]
+pub trait ApiGetExt<K> {
+    /// Check whether named object exists, fetching its metadata only
+    ///
+    async fn exists(&self, name: &str) -> client::Result<bool>;
+}
+
+#[async_trait::async_trait]
+impl<K> ApiGetExt<K> for api::Api<K>
+where
+    K: Clone + fmt::Debug + k8s::openapi::serde::de::DeserializeOwned + client::Resource,
+{
+    async fn exists(&self, name: &str) -> client::Result<bool> {
+        self.get_metadata_opt(name)
+            .await
+            .map(|object| object.is_some())
+    }
+}
@@ -32,3 +32,19 @@ pub fn not_found_ok<K>(err: Error) -> client::Result<either::Either<K, Status>>
 pub fn ignore_not_found<K>(err: Error) -> client::Result<either::Either<K, Status>> {
     not_found_ok(err)
 }
+
+/// Client side validation failure reported the same way the API server reports
+/// invalid requests
+pub(crate) fn bad_request(message: &str) -> Error {
+    Error::Api(
+        Status::failure(message, "BadRequest")
+            .with_code(400)
+            .boxed(),
+    )
+}
+
+/// Client side wait that did not complete in time, reported the same way
+/// the API server reports timeouts
+pub(crate) fn timeout(message: &str) -> Error {
+    Error::Api(Status::failure(message, "Timeout").with_code(504).boxed())
+}
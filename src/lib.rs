@@ -22,25 +22,84 @@
 
 use k8s_openapi_ext as k8s;
 
+use k8s::admissionregistrationv1;
 use k8s::apiextensionsv1;
 use k8s::apiregistrationv1;
 use k8s::appsv1;
 use k8s::autoscalingv2;
 use k8s::batchv1;
+use k8s::coordinationv1;
 use k8s::corev1;
+use k8s::metav1;
+use k8s::nodev1;
+use k8s::openapi::api::discovery::v1 as discoveryv1;
+use k8s::openapi::api::networking::v1 as networkingv1;
 use k8s::rbacv1;
+use k8s::resource;
+use k8s::schedulingv1;
 use k8s::storagev1;
-// use k8s::metav1;
 use kube_client as client;
 
 use client::api;
 
+pub use age::is_older_than;
+pub use age::resource_age;
+#[cfg(feature = "cache")]
+pub use cache::Cached;
+pub use diff::JsonChange;
+pub use diff::JsonDiff;
+pub use error::BulkApplyError;
+pub use error::KubeExtError;
 pub use ext::KubeClientExt;
 pub use ext2::KubeClientExt2;
+pub use get::ApiGetExt;
 #[expect(deprecated)]
 pub use helper::ignore_not_found;
 pub use helper::not_found_ok;
+#[cfg(feature = "metrics")]
+pub use metrics::MetricsLayer;
+#[cfg(feature = "metrics")]
+pub use metrics::MetricsRecorder;
+#[cfg(feature = "metrics")]
+pub use metrics::MetricsService;
+#[cfg(feature = "metrics")]
+pub use metrics::OpenTelemetryRecorder;
+pub use quantity::QuantityParseError;
+pub use quantity::parse_cpu;
+pub use quantity::parse_memory;
+pub use quantity::try_parse_cpu;
+pub use quantity::try_parse_memory;
+pub use retry::MAX_RETRY_AFTER;
+pub use retry::RetryPolicy;
+pub use selector::FieldSelector;
+pub use selector::LabelSelector;
+pub use selector::SelectorError;
+pub use selector::selector_matches;
+pub use types::ContainerStateKind;
+pub use types::EndpointHealth;
+pub use types::JobState;
+pub use types::JobStats;
+pub use types::KubectlAll;
+pub use types::NamespaceSummary;
+pub use types::NodeUtilization;
+pub use types::OwnerNode;
+pub use types::PodMounts;
+pub use types::QosClass;
+pub use types::ResourceTotals;
+pub use types::Workload;
 
+mod age;
+#[cfg(feature = "cache")]
+mod cache;
+mod diff;
+mod error;
 mod ext;
 mod ext2;
+mod get;
 mod helper;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod quantity;
+mod retry;
+mod selector;
+mod types;
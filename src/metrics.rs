@@ -0,0 +1,331 @@
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use futures::FutureExt as _;
+use futures::future::BoxFuture;
+use http::Request;
+use http::Response;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Counter;
+use opentelemetry::metrics::Histogram;
+use opentelemetry::metrics::Meter;
+use tower::Layer;
+use tower::Service;
+
+/// Sink for per-call API metrics
+///
+/// Invoked by [`MetricsLayer`] once for every request sent by the client with
+/// the request verb (`get`, `list`, `watch`, `create`, `replace`, `patch`, `delete`
+/// or `deletecollection`), the resource (e.g. `pods` or `deployments/scale`, empty for
+/// non-resource requests such as `/readyz` or discovery), the call result
+/// (`ok` or `error`) and the time it took to receive the response headers.
+///
+pub trait MetricsRecorder: Send + Sync {
+    fn record(&self, verb: &str, resource: &str, result: &str, elapsed: Duration);
+}
+
+/// [`tower::Layer`] reporting every API call made through a `kube::Client`
+/// to a [`MetricsRecorder`]
+///
+/// Being part of the client service stack, it records all the requests, whether
+/// they are issued by the helpers of this crate or directly through `kube::Api`.
+/// Each client can have its own recorder.
+///
+/// ```no_run
+/// # use kube_client::{Client, Config, client::ClientBuilder};
+/// use kube_client_ext::{MetricsLayer, OpenTelemetryRecorder};
+///
+/// # async fn client(config: Config) -> kube_client::Result<Client> {
+/// let meter = opentelemetry::global::meter("my-tool");
+/// let layer = MetricsLayer::new(OpenTelemetryRecorder::new(&meter));
+/// let client = ClientBuilder::try_from(config)?.with_layer(&layer).build();
+/// # Ok(client)
+/// # }
+/// ```
+///
+#[derive(Clone)]
+pub struct MetricsLayer {
+    recorder: Arc<dyn MetricsRecorder>,
+}
+
+impl MetricsLayer {
+    pub fn new(recorder: impl MetricsRecorder + 'static) -> Self {
+        Self {
+            recorder: Arc::new(recorder),
+        }
+    }
+}
+
+impl std::fmt::Debug for MetricsLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricsLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = MetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService {
+            inner,
+            recorder: self.recorder.clone(),
+        }
+    }
+}
+
+/// Service created by [`MetricsLayer`]
+#[derive(Clone)]
+pub struct MetricsService<S> {
+    inner: S,
+    recorder: Arc<dyn MetricsRecorder>,
+}
+
+impl<S: std::fmt::Debug> std::fmt::Debug for MetricsService<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricsService")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, B, R> Service<Request<B>> for MetricsService<S>
+where
+    S: Service<Request<B>, Response = Response<R>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let (verb, resource) = request_info(
+            request.method(),
+            request.uri().path(),
+            request.uri().query(),
+        );
+        let recorder = self.recorder.clone();
+        let start = Instant::now();
+        let call = self.inner.call(request);
+        async move {
+            let response = call.await;
+            let ok = response.as_ref().is_ok_and(|response| {
+                !(response.status().is_client_error() || response.status().is_server_error())
+            });
+            let result = if ok { "ok" } else { "error" };
+            recorder.record(verb, &resource, result, start.elapsed());
+            response
+        }
+        .boxed()
+    }
+}
+
+/// [`MetricsRecorder`] reporting to an OpenTelemetry [`Meter`]
+///
+/// Records the `kube_client_ext.api.calls` counter and the
+/// `kube_client_ext.api.duration` histogram (in seconds), both with
+/// `verb`, `resource` and `result` attributes.
+///
+#[derive(Clone, Debug)]
+pub struct OpenTelemetryRecorder {
+    calls: Counter<u64>,
+    duration: Histogram<f64>,
+}
+
+impl OpenTelemetryRecorder {
+    pub fn new(meter: &Meter) -> Self {
+        let calls = meter
+            .u64_counter("kube_client_ext.api.calls")
+            .with_description("Number of Kubernetes API calls")
+            .build();
+        let duration = meter
+            .f64_histogram("kube_client_ext.api.duration")
+            .with_description("Duration of Kubernetes API calls")
+            .with_unit("s")
+            .build();
+        Self { calls, duration }
+    }
+}
+
+impl MetricsRecorder for OpenTelemetryRecorder {
+    fn record(&self, verb: &str, resource: &str, result: &str, elapsed: Duration) {
+        let attributes = [
+            KeyValue::new("verb", verb.to_string()),
+            KeyValue::new("resource", resource.to_string()),
+            KeyValue::new("result", result.to_string()),
+        ];
+        self.calls.add(1, &attributes);
+        self.duration.record(elapsed.as_secs_f64(), &attributes);
+    }
+}
+
+/// API verb and resource (with subresource, if any) of a request,
+/// following the rules of the API server
+fn request_info(method: &http::Method, path: &str, query: Option<&str>) -> (&'static str, String) {
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let segments = match segments.as_slice() {
+        ["api", _version, rest @ ..] => rest,
+        ["apis", _group, _version, rest @ ..] => rest,
+        _ => &[],
+    };
+    let segments = match segments {
+        ["namespaces", _, rest @ ..] if !matches!(rest, [] | ["status" | "finalize"]) => rest,
+        segments => segments,
+    };
+    let (resource, named) = match segments {
+        [] => (String::new(), false),
+        [resource] => (resource.to_string(), false),
+        [resource, _name] => (resource.to_string(), true),
+        [resource, _name, subresource, ..] => (format!("{resource}/{subresource}"), true),
+    };
+    let watch = query.is_some_and(|query| {
+        query
+            .split('&')
+            .any(|param| matches!(param, "watch=true" | "watch=1"))
+    });
+    let verb = match *method {
+        http::Method::GET if watch => "watch",
+        http::Method::GET if named || resource.is_empty() => "get",
+        http::Method::GET => "list",
+        http::Method::POST => "create",
+        http::Method::PUT => "replace",
+        http::Method::PATCH => "patch",
+        http::Method::DELETE if named => "delete",
+        http::Method::DELETE => "deletecollection",
+        _ => "other",
+    };
+    (verb, resource)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use http::Method;
+    use kube_client::client::Body;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Calls(Mutex<Vec<(String, String, String)>>);
+
+    impl MetricsRecorder for Arc<Calls> {
+        fn record(&self, verb: &str, resource: &str, result: &str, _elapsed: Duration) {
+            let call = (verb.to_string(), resource.to_string(), result.to_string());
+            self.0.lock().unwrap().push(call);
+        }
+    }
+
+    #[tokio::test]
+    async fn records_client_calls() {
+        let (service, mut handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        tokio::spawn(async move {
+            let (_, send) = handle.next_request().await.expect("request");
+            let status = br#"{"kind":"Status","apiVersion":"v1","status":"Failure","reason":"NotFound","code":404}"#;
+            let response = Response::builder()
+                .status(404)
+                .body(Body::from(status.to_vec()));
+            send.send_response(response.unwrap());
+        });
+        let calls = Arc::new(Calls::default());
+        let layer = MetricsLayer::new(calls.clone());
+        let client = kube_client::Client::new(layer.layer(service), "default");
+        let pods = kube_client::Api::<k8s_openapi::api::core::v1::Pod>::default_namespaced(client);
+        assert!(pods.get_opt("web-0").await.unwrap().is_none());
+
+        let calls = calls.0.lock().unwrap().clone();
+        let expected = ("get".to_string(), "pods".to_string(), "error".to_string());
+        assert_eq!(calls, [expected]);
+    }
+
+    fn info(method: Method, path: &str, query: Option<&str>) -> (&'static str, String) {
+        request_info(&method, path, query)
+    }
+
+    #[test]
+    fn namespaced_resources() {
+        assert_eq!(
+            info(Method::GET, "/api/v1/namespaces/default/pods", None),
+            ("list", "pods".to_string())
+        );
+        assert_eq!(
+            info(Method::GET, "/api/v1/namespaces/default/pods/web-0", None),
+            ("get", "pods".to_string())
+        );
+        assert_eq!(
+            info(
+                Method::GET,
+                "/api/v1/namespaces/default/pods/web-0/log",
+                None
+            ),
+            ("get", "pods/log".to_string())
+        );
+        assert_eq!(
+            info(
+                Method::PATCH,
+                "/apis/apps/v1/namespaces/default/deployments/web/scale",
+                None
+            ),
+            ("patch", "deployments/scale".to_string())
+        );
+        assert_eq!(
+            info(Method::DELETE, "/api/v1/namespaces/default/pods", None),
+            ("deletecollection", "pods".to_string())
+        );
+    }
+
+    #[test]
+    fn namespaces() {
+        assert_eq!(
+            info(Method::GET, "/api/v1/namespaces", None),
+            ("list", "namespaces".to_string())
+        );
+        assert_eq!(
+            info(Method::DELETE, "/api/v1/namespaces/default", None),
+            ("delete", "namespaces".to_string())
+        );
+        assert_eq!(
+            info(Method::PUT, "/api/v1/namespaces/default/finalize", None),
+            ("replace", "namespaces/finalize".to_string())
+        );
+    }
+
+    #[test]
+    fn cluster_resources_and_watches() {
+        assert_eq!(
+            info(
+                Method::POST,
+                "/apis/rbac.authorization.k8s.io/v1/clusterroles",
+                None
+            ),
+            ("create", "clusterroles".to_string())
+        );
+        assert_eq!(
+            info(
+                Method::GET,
+                "/api/v1/nodes",
+                Some("watch=true&resourceVersion=10")
+            ),
+            ("watch", "nodes".to_string())
+        );
+    }
+
+    #[test]
+    fn non_resource_requests() {
+        assert_eq!(info(Method::GET, "/readyz", None), ("get", String::new()));
+        assert_eq!(info(Method::GET, "/apis", None), ("get", String::new()));
+        assert_eq!(
+            info(Method::GET, "/apis/apps/v1", None),
+            ("get", String::new())
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+
+use super::*;
+
+/// Parse `quantity` into its value expressed in base units (cores, bytes, etc.)
+///
+/// Understands decimal SI (`n`, `u`, `m`, `k`, `M`, `G`, `T`, `P`, `E`),
+/// binary SI (`Ki`, `Mi`, `Gi`, `Ti`, `Pi`, `Ei`) and exponent (`e3`, `E-2`) suffixes
+fn parse_quantity(quantity: &resource::Quantity) -> Option<f64> {
+    let text = quantity.0.trim();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '+' | '-')))
+        .unwrap_or(text.len());
+    let (number, suffix) = text.split_at(split);
+    let number = number.parse::<f64>().ok()?;
+    let multiplier = match suffix {
+        "" => 1.0,
+        "n" => 1e-9,
+        "u" => 1e-6,
+        "m" => 1e-3,
+        "k" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        "P" => 1e15,
+        "E" => 1e18,
+        "Ki" => 1024_f64,
+        "Mi" => 1024_f64.powi(2),
+        "Gi" => 1024_f64.powi(3),
+        "Ti" => 1024_f64.powi(4),
+        "Pi" => 1024_f64.powi(5),
+        "Ei" => 1024_f64.powi(6),
+        exponent if exponent.starts_with(['e', 'E']) => {
+            10_f64.powi(exponent[1..].parse::<i32>().ok()?)
+        }
+        _ => return None,
+    };
+    Some(number * multiplier)
+}
+
+/// Parse CPU `quantity` (e.g. `100m`, `1.5`, `2`) into cores
+/// Unparseable quantities are treated as zero, use [`try_parse_cpu`] to detect them
+///
+/// ```
+/// # use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+/// use kube_client_ext::parse_cpu;
+///
+/// assert_eq!(parse_cpu(&Quantity("100m".to_string())), 0.1);
+/// assert_eq!(parse_cpu(&Quantity("1.5".to_string())), 1.5);
+/// assert_eq!(parse_cpu(&Quantity("two".to_string())), 0.0);
+/// ```
+///
+pub fn parse_cpu(quantity: &resource::Quantity) -> f64 {
+    try_parse_cpu(quantity).unwrap_or_default()
+}
+
+/// Parse memory `quantity` (e.g. `512Mi`, `1G`, `128974848`, `129e6`) into bytes,
+/// rounding fractional bytes up
+/// Unparseable quantities are treated as zero, use [`try_parse_memory`] to detect them
+///
+/// ```
+/// # use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+/// use kube_client_ext::parse_memory;
+///
+/// assert_eq!(parse_memory(&Quantity("512Mi".to_string())), 536_870_912);
+/// assert_eq!(parse_memory(&Quantity("1G".to_string())), 1_000_000_000);
+/// assert_eq!(parse_memory(&Quantity("129e6".to_string())), 129_000_000);
+/// ```
+///
+pub fn parse_memory(quantity: &resource::Quantity) -> i64 {
+    try_parse_memory(quantity).unwrap_or_default()
+}
+
+/// Parse CPU `quantity` into cores
+///
+/// ```
+/// # use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+/// use kube_client_ext::try_parse_cpu;
+///
+/// assert_eq!(try_parse_cpu(&Quantity("250m".to_string())), Ok(0.25));
+/// let err = try_parse_cpu(&Quantity("lots".to_string())).unwrap_err();
+/// assert_eq!(err.quantity(), "lots");
+/// ```
+///
+pub fn try_parse_cpu(quantity: &resource::Quantity) -> Result<f64, QuantityParseError> {
+    parse_quantity(quantity).ok_or_else(|| QuantityParseError::new(quantity))
+}
+
+/// Parse memory `quantity` into bytes, rounding fractional bytes up
+///
+#[expect(clippy::cast_possible_truncation)]
+pub fn try_parse_memory(quantity: &resource::Quantity) -> Result<i64, QuantityParseError> {
+    parse_quantity(quantity)
+        .map(|bytes| bytes.ceil() as i64)
+        .ok_or_else(|| QuantityParseError::new(quantity))
+}
+
+/// Quantity that is not a valid Kubernetes resource quantity
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuantityParseError {
+    quantity: String,
+}
+
+impl QuantityParseError {
+    fn new(quantity: &resource::Quantity) -> Self {
+        Self {
+            quantity: quantity.0.clone(),
+        }
+    }
+
+    /// The offending quantity text
+    pub fn quantity(&self) -> &str {
+        &self.quantity
+    }
+}
+
+impl fmt::Display for QuantityParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid quantity {:?}", self.quantity)
+    }
+}
+
+impl std::error::Error for QuantityParseError {}
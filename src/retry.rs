@@ -0,0 +1,197 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher as _;
+use std::time::Duration;
+
+use client::Error;
+
+use super::*;
+
+/// Retry policy used by [`KubeClientExt2::with_retries`]
+///
+/// Retries API errors with one of the retryable status codes
+/// (429, 500 and 503 by default) as well as transport level errors
+/// (connection resets and such). Delays grow exponentially from `base_delay`
+/// with jitter applied, and are capped at `max_delay`.
+/// When the API server suggests a delay on 429 it is honored instead,
+/// subject to the same cap.
+///
+/// The `Retry-After` response header is not visible through [`client::Error`],
+/// so the suggested delay is taken from `retryAfterSeconds` of the returned `Status`.
+///
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    retryable: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+            retryable: vec![429, 500, 503],
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total number of attempts, including the first one
+    pub fn max_attempts(self, max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..self
+        }
+    }
+
+    pub fn base_delay(self, base_delay: Duration) -> Self {
+        Self { base_delay, ..self }
+    }
+
+    pub fn max_delay(self, max_delay: Duration) -> Self {
+        Self { max_delay, ..self }
+    }
+
+    /// Replace the set of HTTP status codes that are considered retryable
+    pub fn retryable_codes(self, codes: impl IntoIterator<Item = u16>) -> Self {
+        let retryable = codes.into_iter().collect();
+        Self { retryable, ..self }
+    }
+
+    fn is_retryable(&self, err: &Error) -> bool {
+        match err {
+            Error::Api(status) => self.retryable.contains(&status.code),
+            Error::HyperError(_) | Error::Service(_) => true,
+            _ => false,
+        }
+    }
+
+    fn delay(&self, attempt: u32, err: &Error) -> Duration {
+        let delay = retry_after(err).unwrap_or_else(|| self.backoff(attempt));
+        delay.min(self.max_delay)
+    }
+
+    /// Jittered exponential delay before the given (1-based) retry `attempt`
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1)));
+        jitter(backoff.min(self.max_delay))
+    }
+
+    pub(crate) async fn retry<T, F, Fut>(&self, f: F) -> client::Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = client::Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match f().await {
+                Err(err) if attempt < self.max_attempts && self.is_retryable(&err) => {
+                    tokio::time::sleep(self.delay(attempt, &err)).await;
+                    attempt += 1;
+                }
+                other => return other,
+            }
+        }
+    }
+}
+
+/// Upper bound for the server suggested delay honored by
+/// [`KubeClientExt2::with_throttle_retry`]
+///
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Delay used for throttled (429) requests the API server did not suggest a delay for
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Call `f` and, if it was throttled (429), sleep for the suggested delay
+/// (or [`DEFAULT_RETRY_AFTER`] without one, capped at `max_delay`) and call it once more
+pub(crate) async fn retry_throttled<T, F, Fut>(max_delay: Duration, f: F) -> client::Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = client::Result<T>>,
+{
+    match f().await {
+        Err(Error::Api(status)) if status.code == 429 => {
+            let delay = retry_after_seconds(&status).unwrap_or(DEFAULT_RETRY_AFTER);
+            tokio::time::sleep(delay.min(max_delay)).await;
+            f().await
+        }
+        other => other,
+    }
+}
+
+/// Delay suggested by the API server for throttled (429) requests
+fn retry_after(err: &Error) -> Option<Duration> {
+    match err {
+        Error::Api(status) if status.code == 429 => retry_after_seconds(status),
+        _ => None,
+    }
+}
+
+/// `retryAfterSeconds` reported in the details of `status`, if any
+/// (API Priority and Fairness rejections often do not set it)
+fn retry_after_seconds(status: &client::core::Status) -> Option<Duration> {
+    status
+        .details
+        .as_ref()
+        .map(|details| details.retry_after_seconds)
+        .filter(|seconds| *seconds > 0)
+        .map(|seconds| Duration::from_secs(seconds.into()))
+}
+
+/// Pick a random delay between half and the full `delay`
+fn jitter(delay: Duration) -> Duration {
+    let random = RandomState::new().hash_one(0_u8);
+    let half = delay / 2;
+    let nanos = u64::try_from(half.as_nanos()).unwrap_or(u64::MAX);
+    half + Duration::from_nanos(random.checked_rem(nanos).unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_between(delay: Duration, min: Duration, max: Duration) {
+        assert!(
+            min <= delay && delay <= max,
+            "{delay:?} not within {min:?}..={max:?}"
+        );
+    }
+
+    #[test]
+    fn backoff_grows_within_jitter_bounds() {
+        let policy = RetryPolicy::new().base_delay(Duration::from_millis(100));
+        for _ in 0..100 {
+            for (attempt, full) in [(1, 100), (2, 200), (3, 400), (6, 3200)] {
+                let full = Duration::from_millis(full);
+                assert_between(policy.backoff(attempt), full / 2, full);
+            }
+        }
+    }
+
+    #[test]
+    fn backoff_is_capped_at_max_delay() {
+        let max = Duration::from_secs(30);
+        let policy = RetryPolicy::new().max_delay(max);
+        for attempt in [10, 32, 64, u32::MAX] {
+            assert_between(policy.backoff(attempt), max / 2, max);
+        }
+        let policy = policy.base_delay(Duration::MAX);
+        assert_between(policy.backoff(1), max / 2, max);
+    }
+
+    #[test]
+    fn zero_delay_has_no_jitter() {
+        let policy = RetryPolicy::new().base_delay(Duration::ZERO);
+        assert_eq!(policy.backoff(1), Duration::ZERO);
+        assert_eq!(policy.backoff(5), Duration::ZERO);
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::*;
+
+/// Check whether `labels` satisfy label `selector`, the way the API server does
+///
+/// All of `matchLabels` and all of `matchExpressions` (`In`, `NotIn`, `Exists`
+/// and `DoesNotExist`) must match, so an empty selector matches everything.
+/// `NotIn` matches labels without the key, and expressions with an unknown
+/// operator never match.
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, LabelSelectorRequirement};
+/// use kube_client_ext::selector_matches;
+///
+/// let labels = BTreeMap::from([
+///     ("app".to_string(), "web".to_string()),
+///     ("tier".to_string(), "frontend".to_string()),
+/// ]);
+/// let requirement = |key: &str, operator: &str, values: &[&str]| LabelSelectorRequirement {
+///     key: key.to_string(),
+///     operator: operator.to_string(),
+///     values: Some(values.iter().map(ToString::to_string).collect()),
+/// };
+/// let expressions = |expressions| LabelSelector {
+///     match_expressions: Some(expressions),
+///     ..LabelSelector::default()
+/// };
+///
+/// assert!(selector_matches(&LabelSelector::default(), &labels));
+/// assert!(selector_matches(&LabelSelector::default(), &BTreeMap::new()));
+///
+/// let app = |value: &str| LabelSelector {
+///     match_labels: Some(BTreeMap::from([("app".to_string(), value.to_string())])),
+///     ..LabelSelector::default()
+/// };
+/// assert!(selector_matches(&app("web"), &labels));
+/// assert!(!selector_matches(&app("db"), &labels));
+///
+/// assert!(selector_matches(&expressions(vec![requirement("tier", "In", &["frontend", "backend"])]), &labels));
+/// assert!(!selector_matches(&expressions(vec![requirement("tier", "In", &["backend"])]), &labels));
+/// assert!(!selector_matches(&expressions(vec![requirement("zone", "In", &["a"])]), &labels));
+///
+/// assert!(selector_matches(&expressions(vec![requirement("tier", "NotIn", &["backend"])]), &labels));
+/// assert!(!selector_matches(&expressions(vec![requirement("tier", "NotIn", &["frontend"])]), &labels));
+/// assert!(selector_matches(&expressions(vec![requirement("zone", "NotIn", &["a"])]), &labels));
+///
+/// assert!(selector_matches(&expressions(vec![requirement("app", "Exists", &[])]), &labels));
+/// assert!(!selector_matches(&expressions(vec![requirement("zone", "Exists", &[])]), &labels));
+///
+/// assert!(selector_matches(&expressions(vec![requirement("zone", "DoesNotExist", &[])]), &labels));
+/// assert!(!selector_matches(&expressions(vec![requirement("app", "DoesNotExist", &[])]), &labels));
+///
+/// assert!(!selector_matches(&expressions(vec![requirement("app", "Gt", &["1"])]), &labels));
+///
+/// let both = LabelSelector {
+///     match_expressions: Some(vec![requirement("tier", "In", &["backend"])]),
+///     ..app("web")
+/// };
+/// assert!(!selector_matches(&both, &labels));
+/// ```
+///
+pub fn selector_matches(
+    selector: &metav1::LabelSelector,
+    labels: &BTreeMap<String, String>,
+) -> bool {
+    let match_labels = selector
+        .match_labels
+        .iter()
+        .flatten()
+        .all(|(key, value)| labels.get(key) == Some(value));
+    let match_expressions = selector
+        .match_expressions
+        .iter()
+        .flatten()
+        .all(|requirement| requirement_matches(requirement, labels));
+    match_labels && match_expressions
+}
+
+fn requirement_matches(
+    requirement: &metav1::LabelSelectorRequirement,
+    labels: &BTreeMap<String, String>,
+) -> bool {
+    let value = labels.get(&requirement.key);
+    let values = requirement.values.as_deref().unwrap_or_default();
+    match requirement.operator.as_str() {
+        "In" => value.is_some_and(|value| values.contains(value)),
+        "NotIn" => value.is_none_or(|value| !values.contains(value)),
+        "Exists" => value.is_some(),
+        "DoesNotExist" => value.is_none(),
+        _ => false,
+    }
+}
+
+/// Builder of field selectors (e.g. `spec.nodeName=node-1,status.phase!=Failed`)
+/// in the form expected by [`api::ListParams::fields`]
+///
+/// Values are escaped, so they may contain `,`, `=` or `\`, while keys are validated
+/// to be non-empty field paths without whitespace or selector syntax characters.
+///
+/// ```
+/// use kube_client_ext::FieldSelector;
+///
+/// let selector = FieldSelector::new()
+///     .equal("spec.nodeName", "node-1")
+///     .not_equal("status.phase", "Failed")
+///     .build()
+///     .unwrap();
+/// assert_eq!(selector, "spec.nodeName=node-1,status.phase!=Failed");
+///
+/// let selector = FieldSelector::new().equal("metadata.name", "a=b,c").build().unwrap();
+/// assert_eq!(selector, r"metadata.name=a\=b\,c");
+///
+/// assert!(FieldSelector::new().equal("spec nodeName", "node-1").build().is_err());
+/// assert_eq!(FieldSelector::new().build().unwrap(), "");
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldSelector {
+    terms: Vec<String>,
+    error: Option<SelectorError>,
+}
+
+impl FieldSelector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require field `key` to be equal to `value`
+    pub fn equal(self, key: &str, value: &str) -> Self {
+        self.term(key, "=", value)
+    }
+
+    /// Require field `key` not to be equal to `value`
+    pub fn not_equal(self, key: &str, value: &str) -> Self {
+        self.term(key, "!=", value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.error.is_none()
+    }
+
+    /// Render the selector, failing on the first invalid key
+    pub fn build(&self) -> Result<String, SelectorError> {
+        match &self.error {
+            Some(err) => Err(err.clone()),
+            None => Ok(self.terms.join(",")),
+        }
+    }
+
+    fn term(mut self, key: &str, operator: &str, value: &str) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        let invalid = |c: char| c.is_whitespace() || matches!(c, ',' | '=' | '!' | '\\');
+        if key.is_empty() || key.contains(invalid) {
+            self.error = Some(SelectorError::new(format!("invalid field {key:?}")));
+        } else {
+            let value = value
+                .replace('\\', r"\\")
+                .replace(',', r"\,")
+                .replace('=', r"\=");
+            self.terms.push(format!("{key}{operator}{value}"));
+        }
+        self
+    }
+}
+
+/// Selector that cannot be rendered, i.e. has an invalid key or value
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectorError {
+    message: String,
+}
+
+impl SelectorError {
+    fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid selector: {}", self.message)
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+/// Builder of label selectors (e.g. `app=web,tier in (frontend,backend),!canary`)
+/// in the form expected by [`api::ListParams::labels`]
+///
+/// Label selectors have no escaping, so keys and values are validated against
+/// the label syntax instead (`[prefix/]name` keys, values of at most 63 alphanumeric,
+/// `-`, `_` or `.` characters), and the first invalid one fails [`Self::build`].
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// use kube_client_ext::LabelSelector;
+///
+/// let selector = LabelSelector::new()
+///     .equal("app", "web")
+///     .is_in("tier", ["frontend", "backend"])
+///     .not_in("track", ["canary"])
+///     .exists("app.kubernetes.io/name")
+///     .does_not_exist("legacy")
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     selector,
+///     "app=web,tier in (frontend,backend),track notin (canary),app.kubernetes.io/name,!legacy"
+/// );
+///
+/// let labels = BTreeMap::from([("app".to_string(), "web".to_string())]);
+/// let selector = LabelSelector::from(labels).not_equal("tier", "db").build().unwrap();
+/// assert_eq!(selector, "app=web,tier!=db");
+///
+/// assert!(LabelSelector::new().equal("app", "web,tier=db").build().is_err());
+/// assert!(LabelSelector::new().exists("Example.com/app").build().is_err());
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LabelSelector {
+    terms: Vec<String>,
+    error: Option<SelectorError>,
+}
+
+impl LabelSelector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require label `key` to be equal to `value`
+    pub fn equal(self, key: &str, value: &str) -> Self {
+        self.term(key, [value], |key, value| format!("{key}={value}"))
+    }
+
+    /// Require label `key` to be missing or not equal to `value`
+    pub fn not_equal(self, key: &str, value: &str) -> Self {
+        self.term(key, [value], |key, value| format!("{key}!={value}"))
+    }
+
+    /// Require label `key` to be one of `values`
+    pub fn is_in<'a>(self, key: &str, values: impl IntoIterator<Item = &'a str>) -> Self {
+        self.term(key, values, |key, values| format!("{key} in ({values})"))
+    }
+
+    /// Require label `key` to be missing or none of `values`
+    pub fn not_in<'a>(self, key: &str, values: impl IntoIterator<Item = &'a str>) -> Self {
+        self.term(key, values, |key, values| format!("{key} notin ({values})"))
+    }
+
+    /// Require label `key` to be present
+    pub fn exists(self, key: &str) -> Self {
+        self.term(key, [], |key, _| key.to_string())
+    }
+
+    /// Require label `key` to be missing
+    pub fn does_not_exist(self, key: &str) -> Self {
+        self.term(key, [], |key, _| format!("!{key}"))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.error.is_none()
+    }
+
+    /// Render the selector, failing on the first invalid key or value
+    pub fn build(&self) -> Result<String, SelectorError> {
+        match &self.error {
+            Some(err) => Err(err.clone()),
+            None => Ok(self.terms.join(",")),
+        }
+    }
+
+    fn term<'a>(
+        mut self,
+        key: &str,
+        values: impl IntoIterator<Item = &'a str>,
+        render: impl FnOnce(&str, &str) -> String,
+    ) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        let values = values.into_iter().collect::<Vec<_>>();
+        if !is_label_key(key) {
+            self.error = Some(SelectorError::new(format!("invalid label key {key:?}")));
+        } else if let Some(value) = values.iter().find(|value| !is_label_value(value)) {
+            self.error = Some(SelectorError::new(format!("invalid label value {value:?}")));
+        } else {
+            self.terms.push(render(key, &values.join(",")));
+        }
+        self
+    }
+}
+
+impl From<BTreeMap<String, String>> for LabelSelector {
+    fn from(labels: BTreeMap<String, String>) -> Self {
+        labels.iter().fold(Self::new(), |selector, (key, value)| {
+            selector.equal(key, value)
+        })
+    }
+}
+
+/// Label key, i.e. a name with an optional DNS subdomain prefix
+fn is_label_key(key: &str) -> bool {
+    let (prefix, name) = match key.split_once('/') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, key),
+    };
+    let prefix_ok = prefix.is_none_or(|prefix| {
+        !prefix.is_empty()
+            && prefix.len() <= 253
+            && prefix.split('.').all(|label| {
+                label.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+                    && label.ends_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+                    && label
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            })
+    });
+    prefix_ok && !name.is_empty() && is_label_value(name)
+}
+
+/// Label value, i.e. empty or up to 63 alphanumeric, `-`, `_` and `.` characters,
+/// starting and ending with an alphanumeric one
+fn is_label_value(value: &str) -> bool {
+    value.is_empty()
+        || (value.len() <= 63
+            && value.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && value.ends_with(|c: char| c.is_ascii_alphanumeric())
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("app".to_string(), "web".to_string()),
+            ("tier".to_string(), "frontend".to_string()),
+        ])
+    }
+
+    fn requirement(key: &str, operator: &str, values: &[&str]) -> metav1::LabelSelectorRequirement {
+        metav1::LabelSelectorRequirement {
+            key: key.to_string(),
+            operator: operator.to_string(),
+            values: Some(values.iter().map(ToString::to_string).collect()),
+        }
+    }
+
+    fn expression(key: &str, operator: &str, values: &[&str]) -> metav1::LabelSelector {
+        metav1::LabelSelector {
+            match_expressions: Some(vec![requirement(key, operator, values)]),
+            ..metav1::LabelSelector::default()
+        }
+    }
+
+    #[test]
+    fn missing_key() {
+        for labels in [labels(), BTreeMap::new()] {
+            assert!(selector_matches(
+                &expression("zone", "NotIn", &["a"]),
+                &labels
+            ));
+            assert!(selector_matches(
+                &expression("zone", "DoesNotExist", &[]),
+                &labels
+            ));
+        }
+    }
+
+    #[test]
+    fn unknown_operator() {
+        assert!(!selector_matches(
+            &expression("app", "Gt", &["1"]),
+            &labels()
+        ));
+    }
+
+    #[test]
+    fn builders_stop_at_first_error() {
+        let fields = FieldSelector::new()
+            .equal("spec nodeName", "node-1")
+            .equal("status.phase", "Running")
+            .equal("bad,field", "x");
+        assert_eq!(fields.terms, Vec::<String>::new());
+        assert_eq!(
+            fields.build().unwrap_err(),
+            SelectorError::new(r#"invalid field "spec nodeName""#.to_string())
+        );
+
+        let labels = LabelSelector::new()
+            .exists("Example.com/app")
+            .equal("app", "web")
+            .exists("worse key");
+        assert_eq!(labels.terms, Vec::<String>::new());
+        assert_eq!(
+            labels.build().unwrap_err(),
+            SelectorError::new(r#"invalid label key "Example.com/app""#.to_string())
+        );
+    }
+
+    #[test]
+    fn empty_selector() {
+        assert!(selector_matches(
+            &metav1::LabelSelector::default(),
+            &labels()
+        ));
+        assert!(selector_matches(
+            &metav1::LabelSelector::default(),
+            &BTreeMap::new()
+        ));
+    }
+}
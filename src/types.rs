@@ -0,0 +1,810 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::Add;
+use std::sync::Arc;
+
+use super::*;
+
+/// Namespace snapshot equivalent to `kubectl get all`
+///
+#[derive(Clone, Debug, Default)]
+pub struct KubectlAll {
+    pub pods: Vec<corev1::Pod>,
+    pub services: Vec<corev1::Service>,
+    pub deployments: Vec<appsv1::Deployment>,
+    pub replicasets: Vec<appsv1::ReplicaSet>,
+    pub statefulsets: Vec<appsv1::StatefulSet>,
+    pub daemonsets: Vec<appsv1::DaemonSet>,
+    pub jobs: Vec<batchv1::Job>,
+    pub cronjobs: Vec<batchv1::CronJob>,
+    pub replicationcontrollers: Vec<corev1::ReplicationController>,
+}
+
+/// Top level workload controlling a pod
+///
+#[derive(Clone, Debug)]
+#[expect(clippy::large_enum_variant)]
+pub enum Workload {
+    CronJob(batchv1::CronJob),
+    DaemonSet(appsv1::DaemonSet),
+    Deployment(appsv1::Deployment),
+    Job(batchv1::Job),
+    ReplicaSet(appsv1::ReplicaSet),
+    ReplicationController(corev1::ReplicationController),
+    StatefulSet(appsv1::StatefulSet),
+}
+
+impl Workload {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::CronJob(_) => "CronJob",
+            Self::DaemonSet(_) => "DaemonSet",
+            Self::Deployment(_) => "Deployment",
+            Self::Job(_) => "Job",
+            Self::ReplicaSet(_) => "ReplicaSet",
+            Self::ReplicationController(_) => "ReplicationController",
+            Self::StatefulSet(_) => "StatefulSet",
+        }
+    }
+
+    pub fn metadata(&self) -> &metav1::ObjectMeta {
+        match self {
+            Self::CronJob(cronjob) => &cronjob.metadata,
+            Self::DaemonSet(daemonset) => &daemonset.metadata,
+            Self::Deployment(deployment) => &deployment.metadata,
+            Self::Job(job) => &job.metadata,
+            Self::ReplicaSet(replicaset) => &replicaset.metadata,
+            Self::ReplicationController(rc) => &rc.metadata,
+            Self::StatefulSet(statefulset) => &statefulset.metadata,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.metadata().name.as_deref().unwrap_or_default()
+    }
+}
+
+/// Digest of `Job` status
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct JobStats {
+    pub state: JobState,
+    pub active: i32,
+    pub succeeded: i32,
+    pub failed: i32,
+    pub start_time: Option<metav1::Time>,
+    pub completion_time: Option<metav1::Time>,
+    /// Completed indexes of an indexed job, in its compressed text format (i.e. `1,3-5`)
+    pub completed_indexes: Option<String>,
+}
+
+/// Overall state of a `Job`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobState {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl JobStats {
+    pub(crate) fn new(job: &batchv1::Job) -> Self {
+        let status = job.status.as_ref();
+        let condition = |type_: &str| {
+            status
+                .and_then(|status| status.conditions.as_ref())
+                .into_iter()
+                .flatten()
+                .any(|condition| condition.type_ == type_ && condition.status == "True")
+        };
+        let state = if condition("Complete") {
+            JobState::Succeeded
+        } else if condition("Failed") {
+            JobState::Failed
+        } else {
+            JobState::Running
+        };
+        Self {
+            state,
+            active: status.and_then(|status| status.active).unwrap_or_default(),
+            succeeded: status
+                .and_then(|status| status.succeeded)
+                .unwrap_or_default(),
+            failed: status.and_then(|status| status.failed).unwrap_or_default(),
+            start_time: status.and_then(|status| status.start_time.clone()),
+            completion_time: status.and_then(|status| status.completion_time.clone()),
+            completed_indexes: status
+                .and_then(|status| status.completed_indexes.clone())
+                .filter(|indexes| !indexes.is_empty()),
+        }
+    }
+}
+
+/// Names of the `ConfigMap`s and `Secret`s referenced by a pod, by the way they are referenced
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PodMounts {
+    /// `configMap` volumes
+    pub configmap_volumes: BTreeSet<String>,
+    /// `secret` volumes
+    pub secret_volumes: BTreeSet<String>,
+    /// `configMap` sources of `projected` volumes
+    pub projected_configmaps: BTreeSet<String>,
+    /// `secret` sources of `projected` volumes
+    pub projected_secrets: BTreeSet<String>,
+    /// Container `envFrom` and `env[].valueFrom.configMapKeyRef`
+    pub env_configmaps: BTreeSet<String>,
+    /// Container `envFrom` and `env[].valueFrom.secretKeyRef`
+    pub env_secrets: BTreeSet<String>,
+    /// `imagePullSecrets`
+    pub image_pull_secrets: BTreeSet<String>,
+}
+
+impl PodMounts {
+    pub(crate) fn new(pod: &corev1::Pod) -> Self {
+        let mut mounts = Self::default();
+        let Some(spec) = pod.spec.as_ref() else {
+            return mounts;
+        };
+
+        for volume in spec.volumes.iter().flatten() {
+            if let Some(configmap) = &volume.config_map {
+                mounts.configmap_volumes.insert(configmap.name.clone());
+            }
+            if let Some(name) = volume
+                .secret
+                .as_ref()
+                .and_then(|secret| secret.secret_name.clone())
+            {
+                mounts.secret_volumes.insert(name);
+            }
+            let sources = volume
+                .projected
+                .as_ref()
+                .and_then(|projected| projected.sources.as_ref());
+            for source in sources.into_iter().flatten() {
+                if let Some(configmap) = &source.config_map {
+                    mounts.projected_configmaps.insert(configmap.name.clone());
+                }
+                if let Some(secret) = &source.secret {
+                    mounts.projected_secrets.insert(secret.name.clone());
+                }
+            }
+        }
+
+        let containers = spec
+            .containers
+            .iter()
+            .chain(spec.init_containers.iter().flatten())
+            .map(|container| (&container.env, &container.env_from));
+        let ephemeral = spec
+            .ephemeral_containers
+            .iter()
+            .flatten()
+            .map(|container| (&container.env, &container.env_from));
+        for (env, env_from) in containers.chain(ephemeral) {
+            for source in env_from.iter().flatten() {
+                if let Some(configmap) = &source.config_map_ref {
+                    mounts.env_configmaps.insert(configmap.name.clone());
+                }
+                if let Some(secret) = &source.secret_ref {
+                    mounts.env_secrets.insert(secret.name.clone());
+                }
+            }
+            let sources = env
+                .iter()
+                .flatten()
+                .filter_map(|var| var.value_from.as_ref());
+            for source in sources {
+                if let Some(selector) = &source.config_map_key_ref {
+                    mounts.env_configmaps.insert(selector.name.clone());
+                }
+                if let Some(selector) = &source.secret_key_ref {
+                    mounts.env_secrets.insert(selector.name.clone());
+                }
+            }
+        }
+
+        mounts.image_pull_secrets = spec
+            .image_pull_secrets
+            .iter()
+            .flatten()
+            .map(|secret| secret.name.clone())
+            .collect();
+
+        mounts
+    }
+
+    /// All the referenced `ConfigMap`s
+    pub fn configmaps(&self) -> BTreeSet<&str> {
+        self.configmap_volumes
+            .iter()
+            .chain(&self.projected_configmaps)
+            .chain(&self.env_configmaps)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// All the referenced `Secret`s
+    pub fn secrets(&self) -> BTreeSet<&str> {
+        self.secret_volumes
+            .iter()
+            .chain(&self.projected_secrets)
+            .chain(&self.env_secrets)
+            .chain(&self.image_pull_secrets)
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Number of objects of common kinds in a namespace
+/// Kinds that could not be listed due to missing permissions are `None`
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NamespaceSummary {
+    pub pods: Option<usize>,
+    pub deployments: Option<usize>,
+    pub services: Option<usize>,
+    pub configmaps: Option<usize>,
+    pub secrets: Option<usize>,
+    pub persistentvolumeclaims: Option<usize>,
+}
+
+/// Quality of Service class of a pod, deciding its eviction order under node pressure
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QosClass {
+    /// No container has any CPU or memory request or limit, evicted first
+    BestEffort,
+    /// Neither `BestEffort` nor `Guaranteed`
+    Burstable,
+    /// Every container has CPU and memory limits equal to its requests, evicted last
+    Guaranteed,
+}
+
+impl QosClass {
+    /// Classify the `pod` from its (regular and init) container resources the way
+    /// the kubelet does, treating missing requests as equal to limits like
+    /// the API server defaulting does
+    pub(crate) fn of_pod(pod: &corev1::Pod) -> Self {
+        let Some(spec) = pod.spec.as_ref() else {
+            return Self::BestEffort;
+        };
+        let mut any = false;
+        let mut guaranteed = true;
+        for container in spec
+            .containers
+            .iter()
+            .chain(spec.init_containers.iter().flatten())
+        {
+            let resources = container.resources.as_ref();
+            let requests = resources.and_then(|resources| resources.requests.as_ref());
+            let limits = resources.and_then(|resources| resources.limits.as_ref());
+            let cpu_request = requests
+                .and_then(|requests| requests.get("cpu"))
+                .map(parse_cpu);
+            let cpu_limit = limits.and_then(|limits| limits.get("cpu")).map(parse_cpu);
+            let memory_request = requests
+                .and_then(|requests| requests.get("memory"))
+                .map(parse_memory);
+            let memory_limit = limits
+                .and_then(|limits| limits.get("memory"))
+                .map(parse_memory);
+
+            any |= cpu_request.is_some_and(|cpu| cpu != 0.0)
+                || cpu_limit.is_some_and(|cpu| cpu != 0.0)
+                || memory_request.is_some_and(|memory| memory != 0)
+                || memory_limit.is_some_and(|memory| memory != 0);
+            guaranteed &= match (cpu_limit, memory_limit) {
+                (Some(cpu_limit), Some(memory_limit)) if cpu_limit != 0.0 && memory_limit != 0 => {
+                    cpu_request.unwrap_or(cpu_limit) == cpu_limit
+                        && memory_request.unwrap_or(memory_limit) == memory_limit
+                }
+                _ => false,
+            };
+        }
+
+        if !any {
+            Self::BestEffort
+        } else if guaranteed {
+            Self::Guaranteed
+        } else {
+            Self::Burstable
+        }
+    }
+}
+
+/// Ready and not ready endpoints of a service, across all of its `EndpointSlice`s
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EndpointHealth {
+    pub ready: usize,
+    pub not_ready: usize,
+}
+
+impl EndpointHealth {
+    /// Tally endpoints of the `slices`, endpoints with unknown readiness count as ready
+    /// (as the `EndpointSlice` API suggests), so dual-stack services count each endpoint
+    /// once per address family
+    pub(crate) fn from_slices(slices: &[discoveryv1::EndpointSlice]) -> Self {
+        slices.iter().flat_map(|slice| &slice.endpoints).fold(
+            Self::default(),
+            |mut health, endpoint| {
+                let ready = endpoint
+                    .conditions
+                    .as_ref()
+                    .and_then(|conditions| conditions.ready)
+                    .unwrap_or(true);
+                if ready {
+                    health.ready += 1;
+                } else {
+                    health.not_ready += 1;
+                }
+                health
+            },
+        )
+    }
+
+    /// Whether there is at least one ready endpoint
+    pub fn is_healthy(&self) -> bool {
+        self.ready > 0
+    }
+
+    pub fn total(&self) -> usize {
+        self.ready + self.not_ready
+    }
+}
+
+/// Object together with all of its (transitive) owners
+///
+/// Returned by [`KubeClientExt2::owner_hierarchy`]. Ancestors shared by several
+/// dependents are the same `Arc`, so the hierarchy is a DAG rather than a tree.
+///
+#[derive(Clone, Debug)]
+pub struct OwnerNode {
+    pub object: api::DynamicObject,
+    pub owners: Vec<Arc<Self>>,
+}
+
+impl OwnerNode {
+    /// Maximum number of owner levels resolved above the starting object
+    pub const MAX_DEPTH: usize = 16;
+
+    /// Topmost owners (objects without resolved owners of their own), without duplicates
+    /// Return the object itself if it has no owners
+    ///
+    pub fn roots(&self) -> Vec<&api::DynamicObject> {
+        let mut seen = HashSet::new();
+        let mut roots = Vec::new();
+        self.collect_roots(&mut seen, &mut roots);
+        roots
+    }
+
+    fn collect_roots<'a>(
+        &'a self,
+        seen: &mut HashSet<*const Self>,
+        roots: &mut Vec<&'a api::DynamicObject>,
+    ) {
+        if !seen.insert(self) {
+            return;
+        }
+        if self.owners.is_empty() {
+            roots.push(&self.object);
+        }
+        for owner in &self.owners {
+            owner.collect_roots(seen, roots);
+        }
+    }
+
+    /// Link `object` with its `owners` (keyed by uid), skipping references forming a cycle
+    pub(crate) fn build(
+        object: api::DynamicObject,
+        owners: &HashMap<String, api::DynamicObject>,
+    ) -> Self {
+        let mut built = HashMap::new();
+        let mut path = object.metadata.uid.iter().cloned().collect();
+        Self::link(object, owners, &mut built, &mut path)
+    }
+
+    fn link(
+        object: api::DynamicObject,
+        all: &HashMap<String, api::DynamicObject>,
+        built: &mut HashMap<String, Arc<Self>>,
+        path: &mut Vec<String>,
+    ) -> Self {
+        let owners = object
+            .metadata
+            .owner_references
+            .iter()
+            .flatten()
+            .filter_map(|owner| {
+                if path.contains(&owner.uid) {
+                    return None;
+                }
+                if let Some(node) = built.get(&owner.uid) {
+                    return Some(Arc::clone(node));
+                }
+                let parent = all.get(&owner.uid)?.clone();
+                path.push(owner.uid.clone());
+                let node = Arc::new(Self::link(parent, all, built, path));
+                path.pop();
+                built.insert(owner.uid.clone(), Arc::clone(&node));
+                Some(node)
+            })
+            .collect();
+        Self { object, owners }
+    }
+}
+
+/// Current state of a container with its reason, if any
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContainerStateKind {
+    /// Not running yet, e.g. `ContainerCreating` or `CrashLoopBackOff`
+    Waiting {
+        reason: Option<String>,
+    },
+    Running,
+    /// Finished, e.g. `Completed`, `Error` or `OOMKilled`
+    Terminated {
+        reason: Option<String>,
+        exit_code: i32,
+    },
+}
+
+impl ContainerStateKind {
+    pub(crate) fn from_state(state: &corev1::ContainerState) -> Option<Self> {
+        if let Some(waiting) = &state.waiting {
+            Some(Self::Waiting {
+                reason: waiting.reason.clone(),
+            })
+        } else if state.running.is_some() {
+            Some(Self::Running)
+        } else {
+            state
+                .terminated
+                .as_ref()
+                .map(|terminated| Self::Terminated {
+                    reason: terminated.reason.clone(),
+                    exit_code: terminated.exit_code,
+                })
+        }
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Waiting { reason } | Self::Terminated { reason, .. } => reason.as_deref(),
+            Self::Running => None,
+        }
+    }
+}
+
+/// CPU and memory totals of a pod's resource requests or limits
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ResourceTotals {
+    /// CPU, in cores
+    pub cpu: f64,
+    /// Memory, in bytes
+    pub memory: i64,
+}
+
+impl ResourceTotals {
+    /// Effective requests of the `pod`
+    pub(crate) fn requests(pod: &corev1::Pod) -> Self {
+        Self::of_pod(pod, |resources| resources.requests.as_ref())
+    }
+
+    /// Effective limits of the `pod`
+    pub(crate) fn limits(pod: &corev1::Pod) -> Self {
+        Self::of_pod(pod, |resources| resources.limits.as_ref())
+    }
+
+    /// Aggregate the way the scheduler does: regular containers and sidecars
+    /// (restartable init containers) run together and are summed, while regular init
+    /// containers run one at a time alongside the sidecars started before them,
+    /// so the larger of the two is taken, plus pod overhead
+    fn of_pod(
+        pod: &corev1::Pod,
+        select: impl Fn(&corev1::ResourceRequirements) -> Option<&BTreeMap<String, resource::Quantity>>,
+    ) -> Self {
+        let Some(spec) = pod.spec.as_ref() else {
+            return Self::default();
+        };
+        let container = |container: &corev1::Container| {
+            container
+                .resources
+                .as_ref()
+                .and_then(&select)
+                .map(Self::from_resources)
+                .unwrap_or_default()
+        };
+
+        let mut sidecars = Self::default();
+        let mut init = Self::default();
+        for init_container in spec.init_containers.iter().flatten() {
+            let totals = container(init_container);
+            if init_container.restart_policy.as_deref() == Some("Always") {
+                sidecars = sidecars + totals;
+                init = init.max(sidecars);
+            } else {
+                init = init.max(sidecars + totals);
+            }
+        }
+        let regular = spec
+            .containers
+            .iter()
+            .map(container)
+            .fold(sidecars, Add::add);
+        let overhead = spec
+            .overhead
+            .as_ref()
+            .map(Self::from_resources)
+            .unwrap_or_default();
+
+        regular.max(init) + overhead
+    }
+
+    /// Actual usage of all the containers reported by `metrics.k8s.io` `PodMetrics`
+    pub(crate) fn usage(metrics: &api::DynamicObject) -> Self {
+        metrics
+            .data
+            .get("containers")
+            .and_then(|containers| containers.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|container| container.get("usage").cloned())
+            .filter_map(|usage| k8s::openapi::serde_json::from_value(usage).ok())
+            .map(|usage| Self::from_resources(&usage))
+            .fold(Self::default(), Add::add)
+    }
+
+    /// Whether either CPU or memory of `self` is above that of `other`
+    pub(crate) fn exceeds(&self, other: &Self) -> bool {
+        self.cpu > other.cpu || self.memory > other.memory
+    }
+
+    fn from_resources(resources: &BTreeMap<String, resource::Quantity>) -> Self {
+        Self {
+            cpu: resources.get("cpu").map(parse_cpu).unwrap_or_default(),
+            memory: resources
+                .get("memory")
+                .map(parse_memory)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        Self {
+            cpu: self.cpu.max(other.cpu),
+            memory: self.memory.max(other.memory),
+        }
+    }
+}
+
+impl Add for ResourceTotals {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            cpu: self.cpu + other.cpu,
+            memory: self.memory + other.memory,
+        }
+    }
+}
+
+/// Resource requests of the pods scheduled to a node compared to its allocatable capacity
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NodeUtilization {
+    /// CPU requested by the pods, in cores
+    pub cpu_requested: f64,
+    /// CPU allocatable on the node, in cores
+    pub cpu_allocatable: f64,
+    /// Memory requested by the pods, in bytes
+    pub memory_requested: i64,
+    /// Memory allocatable on the node, in bytes
+    pub memory_allocatable: i64,
+}
+
+impl NodeUtilization {
+    /// Requested CPU as a percentage of allocatable CPU
+    pub fn cpu_percent(&self) -> f64 {
+        percent(self.cpu_requested, self.cpu_allocatable)
+    }
+
+    /// Requested memory as a percentage of allocatable memory
+    pub fn memory_percent(&self) -> f64 {
+        percent(self.memory_requested as f64, self.memory_allocatable as f64)
+    }
+}
+
+fn percent(part: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        part * 100.0 / total
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s::openapi::serde_json as json;
+
+    use super::*;
+
+    const MI: i64 = 1024 * 1024;
+
+    fn pod(spec: json::Value) -> corev1::Pod {
+        json::from_value(json::json!({ "spec": spec })).unwrap()
+    }
+
+    fn container(name: &str, cpu: &str, memory: &str) -> json::Value {
+        json::json!({
+            "name": name,
+            "resources": { "requests": { "cpu": cpu, "memory": memory } }
+        })
+    }
+
+    fn sidecar(name: &str, cpu: &str, memory: &str) -> json::Value {
+        let mut sidecar = container(name, cpu, memory);
+        sidecar["restartPolicy"] = "Always".into();
+        sidecar
+    }
+
+    #[test]
+    fn init_container_larger_than_regular_containers() {
+        let pod = pod(json::json!({
+            "initContainers": [container("migrate", "2", "128Mi")],
+            "containers": [container("app", "500m", "256Mi"), container("proxy", "250m", "64Mi")]
+        }));
+        let requests = ResourceTotals::requests(&pod);
+        assert_eq!(requests.cpu, 2.0);
+        assert_eq!(requests.memory, 320 * MI);
+    }
+
+    #[test]
+    fn sidecars_add_to_regular_containers() {
+        let pod = pod(json::json!({
+            "initContainers": [sidecar("mesh", "250m", "64Mi")],
+            "containers": [container("app", "500m", "256Mi")]
+        }));
+        let requests = ResourceTotals::requests(&pod);
+        assert_eq!(requests.cpu, 0.75);
+        assert_eq!(requests.memory, 320 * MI);
+    }
+
+    #[test]
+    fn init_containers_run_alongside_earlier_sidecars_only() {
+        let pod = pod(json::json!({
+            "initContainers": [
+                container("before", "1", "512Mi"),
+                sidecar("mesh", "250m", "64Mi"),
+                container("after", "1", "128Mi"),
+            ],
+            "containers": [container("app", "500m", "256Mi")],
+            "overhead": { "cpu": "250m", "memory": "32Mi" }
+        }));
+        let requests = ResourceTotals::requests(&pod);
+        assert_eq!(requests.cpu, 1.5);
+        assert_eq!(requests.memory, 544 * MI);
+    }
+
+    fn limited(name: &str, requests: json::Value, limits: json::Value) -> json::Value {
+        json::json!({
+            "name": name,
+            "resources": { "requests": requests, "limits": limits }
+        })
+    }
+
+    #[test]
+    fn guaranteed_when_requests_default_from_limits() {
+        let limits = json::json!({ "cpu": "500m", "memory": "256Mi" });
+        let pod = pod(json::json!({
+            "containers": [limited("app", json::json!({}), limits.clone())],
+            "initContainers": [limited("init", json::json!({ "cpu": "0.5" }), limits)]
+        }));
+        assert_eq!(QosClass::of_pod(&pod), QosClass::Guaranteed);
+    }
+
+    #[test]
+    fn burstable_when_requests_differ_from_limits() {
+        let limits = json::json!({ "cpu": "500m", "memory": "256Mi" });
+        let pod = pod(json::json!({
+            "containers": [
+                limited("app", json::json!({}), limits.clone()),
+                limited("proxy", json::json!({ "cpu": "250m" }), limits),
+            ]
+        }));
+        assert_eq!(QosClass::of_pod(&pod), QosClass::Burstable);
+    }
+
+    #[test]
+    fn burstable_when_limits_are_partial() {
+        let pod = pod(json::json!({
+            "containers": [
+                limited("app", json::json!({}), json::json!({ "cpu": "500m", "memory": "256Mi" })),
+                limited("proxy", json::json!({}), json::json!({ "memory": "64Mi" })),
+            ]
+        }));
+        assert_eq!(QosClass::of_pod(&pod), QosClass::Burstable);
+    }
+
+    #[test]
+    fn best_effort_without_resources() {
+        let pod = pod(json::json!({
+            "containers": [{ "name": "app" }, limited("proxy", json::json!({}), json::json!({}))]
+        }));
+        assert_eq!(QosClass::of_pod(&pod), QosClass::BestEffort);
+    }
+
+    fn owned(uid: &str, owners: &[&str]) -> api::DynamicObject {
+        let references = owners
+            .iter()
+            .map(|owner| {
+                json::json!({ "apiVersion": "v1", "kind": "Fake", "name": owner, "uid": owner })
+            })
+            .collect::<Vec<_>>();
+        json::from_value(json::json!({
+            "apiVersion": "v1",
+            "kind": "Fake",
+            "metadata": { "name": uid, "uid": uid, "ownerReferences": references }
+        }))
+        .unwrap()
+    }
+
+    fn owners(objects: &[api::DynamicObject]) -> HashMap<String, api::DynamicObject> {
+        objects
+            .iter()
+            .map(|object| (object.metadata.uid.clone().unwrap(), object.clone()))
+            .collect()
+    }
+
+    fn uids(objects: &[&api::DynamicObject]) -> Vec<String> {
+        objects
+            .iter()
+            .filter_map(|object| object.metadata.uid.clone())
+            .collect()
+    }
+
+    #[test]
+    fn owner_cycles_are_cut() {
+        let all = owners(&[owned("rs", &["deploy"]), owned("deploy", &["rs", "pod"])]);
+        let node = OwnerNode::build(owned("pod", &["rs"]), &all);
+
+        let [rs] = node.owners.as_slice() else {
+            panic!("expected single owner");
+        };
+        let [deploy] = rs.owners.as_slice() else {
+            panic!("expected single owner");
+        };
+        assert_eq!(deploy.object.metadata.uid.as_deref(), Some("deploy"));
+        assert!(deploy.owners.is_empty());
+        assert_eq!(uids(&node.roots()), ["deploy"]);
+    }
+
+    #[test]
+    fn self_owned_object_is_its_own_root() {
+        let node = OwnerNode::build(owned("pod", &["pod"]), &HashMap::new());
+        assert!(node.owners.is_empty());
+        assert_eq!(uids(&node.roots()), ["pod"]);
+    }
+
+    #[test]
+    fn shared_owners_are_linked_once() {
+        let all = owners(&[
+            owned("a", &["root"]),
+            owned("b", &["root"]),
+            owned("root", &[]),
+        ]);
+        let node = OwnerNode::build(owned("pod", &["a", "b", "gone"]), &all);
+
+        let [a, b] = node.owners.as_slice() else {
+            panic!("expected two owners");
+        };
+        assert!(Arc::ptr_eq(&a.owners[0], &b.owners[0]));
+        assert_eq!(uids(&node.roots()), ["root"]);
+    }
+}